@@ -0,0 +1,90 @@
+use crate::{is_ident_continue, is_ident_start, LeadingZeroPolicy, Lexer};
+use huff_utils::prelude::*;
+use std::collections::HashSet;
+
+/// Configures the optional modes of a [Lexer] before building it.
+///
+/// Every mode defaults to the one of `Lexer::new`.
+#[derive(Clone)]
+pub struct LexerBuilder<'a> {
+    /// Whether to emit warnings and error hints
+    strict: bool,
+    /// How decimal literals with a leading zero are interpreted
+    leading_zero: LeadingZeroPolicy,
+    /// The symbols defined for conditional compilation
+    defines: Option<HashSet<&'a str>>,
+    /// Whether a character can start an identifier
+    ident_start: fn(char) -> bool,
+    /// Whether a character can continue an identifier
+    ident_continue: fn(char) -> bool,
+    /// The modifier keywords of macro signatures
+    modifiers: HashSet<&'a str>,
+}
+
+impl Default for LexerBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            strict: false,
+            leading_zero: LeadingZeroPolicy::default(),
+            defines: None,
+            ident_start: is_ident_start,
+            ident_continue: is_ident_continue,
+            modifiers: HashSet::new(),
+        }
+    }
+}
+
+impl<'a> LexerBuilder<'a> {
+    /// Public associated function that instantiates a builder with every mode disabled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Emits warnings and error hints for suspicious source code.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets how decimal literals with a leading zero are interpreted.
+    pub fn leading_zero(mut self, policy: LeadingZeroPolicy) -> Self {
+        self.leading_zero = policy;
+        self
+    }
+
+    /// Lexes `#ifdef` and `#endif` directives, enabling the regions of the given symbols.
+    pub fn defines(mut self, symbols: impl IntoIterator<Item = &'a str>) -> Self {
+        self.defines = Some(symbols.into_iter().collect());
+        self
+    }
+
+    /// Overrides the characters allowed to start an identifier.
+    pub fn ident_start(mut self, pred: fn(char) -> bool) -> Self {
+        self.ident_start = pred;
+        self
+    }
+
+    /// Overrides the characters allowed to continue an identifier.
+    pub fn ident_continue(mut self, pred: fn(char) -> bool) -> Self {
+        self.ident_continue = pred;
+        self
+    }
+
+    /// Sets the modifier keywords lexed as `TokenKind::Modifier` in a macro signature.
+    pub fn modifiers(mut self, modifiers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.modifiers = modifiers.into_iter().collect();
+        self
+    }
+
+    /// Builds a lexer of the source with the configured modes.
+    pub fn build(self, source: FullFileSource<'a>) -> Lexer<'a> {
+        let mut lexer = Lexer::new(source)
+            .with_ident_start(self.ident_start)
+            .with_ident_chars(self.ident_continue)
+            .with_modifiers(self.modifiers);
+        lexer.strict = self.strict;
+        lexer.leading_zero = self.leading_zero;
+        lexer.defines = self.defines;
+        lexer
+    }
+}
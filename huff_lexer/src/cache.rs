@@ -0,0 +1,24 @@
+use huff_utils::prelude::*;
+use std::io::{Read, Write};
+
+/// Serializes a token stream, along with the hash of the source it was lexed from, into a compact
+/// binary form.
+///
+/// Only the offsets of token spans are kept, their file being dropped.
+pub fn serialize_tokens(
+    tokens: &[Token],
+    source_hash: u64,
+    writer: impl Write,
+) -> Result<(), bincode::Error> {
+    let tokens = tokens
+        .iter()
+        .map(|t| Token::new(t.kind.clone(), Span::new(t.span.start..t.span.end, None)))
+        .collect::<Vec<Token>>();
+    bincode::serialize_into(writer, &(source_hash, tokens))
+}
+
+/// Deserializes a token stream serialized by `serialize_tokens`, returning the hash of its
+/// source alongside the tokens.
+pub fn deserialize_tokens(reader: impl Read) -> Result<(u64, Vec<Token>), bincode::Error> {
+    bincode::deserialize_from(reader)
+}
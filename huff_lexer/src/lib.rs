@@ -9,14 +9,34 @@ use huff_utils::prelude::*;
 use regex::Regex;
 use std::{
     cell::{Ref, RefCell, RefMut},
+    collections::HashSet,
     iter::Peekable,
+    ops::Range,
     str::Chars,
 };
 
+/// Token Stream Utilities Module
+pub mod stream;
+pub use stream::*;
+
+/// Multiple Source Lexing Module
+pub mod multi;
+pub use multi::*;
+
+/// Lexer Configuration Module
+pub mod builder;
+pub use builder::*;
+
+/// Token Stream Caching Module
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "cache")]
+pub use cache::*;
+
 /// Defines a context in which the lexing happens.
 /// Allows to differientate between EVM types and opcodes that can either
 /// be identical or the latter being a substring of the former (example : bytes32 and byte)
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Context {
     /// global context
     Global,
@@ -36,13 +56,54 @@ pub enum Context {
     CodeTableBody,
 }
 
+/// Defines how a decimal literal with a leading zero (e.g. `0123`) is interpreted.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LeadingZeroPolicy {
+    /// The leading zero is ignored: `0123` is 123
+    #[default]
+    Decimal,
+    /// The literal is read as octal: `0123` is 83
+    Octal,
+    /// The literal is rejected with a `LexicalErrorKind::LeadingZero` error
+    Reject,
+}
+
+/// An `emit EVENT(args)` statement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmitStmt<'a> {
+    /// The name of the emitted event
+    pub name: &'a str,
+    /// The span of the raw arguments, between the parentheses
+    pub args: Span,
+}
+
+/// A builtin function call taking a macro or table name (e.g. `__codesize(MAIN)`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BuiltinCall<'a> {
+    /// The kind of builtin function
+    pub kind: BuiltinFunctionKind,
+    /// The name of the builtin function, as written
+    pub name: &'a str,
+    /// The identifier passed as the argument
+    pub arg: &'a str,
+}
+
+/// The default rule for the first character of an identifier: a letter or an underscore.
+pub fn is_ident_start(c: char) -> bool {
+    c.is_alphabetic() || c == '_'
+}
+
+/// The default rule for the following characters of an identifier: a letter, a digit or an
+/// underscore.
+pub fn is_ident_continue(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
 /// ## Lexer
 ///
 /// The lexer encapsulated in a struct.
+#[derive(Clone)]
 pub struct Lexer<'a> {
-    /// The source code as peekable chars.
-    /// WARN: SHOULD NEVER BE MODIFIED!
-    pub reference_chars: Peekable<Chars<'a>>,
     /// The source code as peekable chars.
     pub chars: Peekable<Chars<'a>>,
     /// The raw source code.
@@ -52,29 +113,84 @@ pub struct Lexer<'a> {
     /// The previous lexed Token.
     /// NOTE: Cannot be a whitespace.
     pub lookback: Option<Token>,
+    /// The previous lexed Token that is neither a whitespace, a comment, nor EOF.
+    pub last_significant: Option<Token>,
     /// If the lexer has reached the end of file.
     pub eof: bool,
     /// EOF Token has been returned.
     pub eof_returned: bool,
     /// Current context.
     pub context: Context,
+    /// How decimal literals with a leading zero are interpreted.
+    pub leading_zero: LeadingZeroPolicy,
+    /// The end of the span lexed by the previous call to `next`.
+    pub previous_end: usize,
+    /// Whether to emit warnings and error hints for suspicious source code.
+    pub strict: bool,
+    /// The warnings emitted so far.
+    pub warnings: Vec<LexicalWarning>,
+    /// The symbols defined for conditional compilation.
+    /// `#ifdef` and `#endif` directives are only lexed when set.
+    pub defines: Option<HashSet<&'a str>>,
+    /// Whether the next token is a region disabled by an `#ifdef`.
+    pub inactive_region_pending: bool,
+    /// Whether a character can start an identifier.
+    pub ident_start: fn(char) -> bool,
+    /// Whether a character can continue an identifier.
+    pub ident_continue: fn(char) -> bool,
+    /// The modifier keywords recognized between a macro signature and its body.
+    pub modifiers: HashSet<&'a str>,
 }
 
 impl<'a> Lexer<'a> {
     /// Public associated function that instantiates a new lexer.
     pub fn new(source: FullFileSource<'a>) -> Self {
         Self {
-            reference_chars: source.source.chars().peekable(),
             chars: source.source.chars().peekable(),
             source,
             span: RefCell::new(Span::default()),
             lookback: None,
+            last_significant: None,
             eof: false,
             eof_returned: false,
             context: Context::Global,
+            leading_zero: LeadingZeroPolicy::default(),
+            previous_end: 0,
+            strict: false,
+            warnings: vec![],
+            defines: None,
+            inactive_region_pending: false,
+            ident_start: is_ident_start,
+            ident_continue: is_ident_continue,
+            modifiers: HashSet::new(),
         }
     }
 
+    /// Instantiates a builder to configure the optional modes of a lexer.
+    pub fn builder() -> LexerBuilder<'a> {
+        LexerBuilder::new()
+    }
+
+    /// Overrides the characters allowed to continue an identifier, for dialects using characters
+    /// such as `$` or `.` in names.
+    pub fn with_ident_chars(mut self, pred: fn(char) -> bool) -> Self {
+        self.ident_continue = pred;
+        self
+    }
+
+    /// Overrides the characters allowed to start an identifier.
+    pub fn with_ident_start(mut self, pred: fn(char) -> bool) -> Self {
+        self.ident_start = pred;
+        self
+    }
+
+    /// Sets the modifier keywords lexed as `TokenKind::Modifier` in a macro signature, such as
+    /// `unchecked` in `#define macro FOO() = unchecked { ... }`.
+    pub fn with_modifiers(mut self, modifiers: impl IntoIterator<Item = &'a str>) -> Self {
+        self.modifiers = modifiers.into_iter().collect();
+        self
+    }
+
     /// Lex all imports
     /// Example import: `// #include "./Utils.huff"`
     pub fn lex_imports(source: &str) -> Vec<String> {
@@ -166,9 +282,16 @@ impl<'a> Lexer<'a> {
         0
     }
 
-    /// Checks the previous token kind against the input.
+    /// Get the previous lexed token that is neither a whitespace, a comment, nor EOF.
+    pub fn last_significant_token(&self) -> Option<&Token> {
+        self.last_significant.as_ref()
+    }
+
+    /// Checks the previous significant token kind against the input.
+    ///
+    /// Comments are skipped, so that `#define /* doc */ macro` still lexes `macro` as a keyword.
     pub fn checked_lookback(&self, kind: TokenKind) -> bool {
-        self.lookback.clone().and_then(|t| if t.kind == kind { Some(true) } else { None }).is_some()
+        self.last_significant.as_ref().is_some_and(|t| t.kind == kind)
     }
 
     /// Try to peek at the next character from the source
@@ -180,25 +303,30 @@ impl<'a> Lexer<'a> {
     pub fn dyn_peek(&mut self, f: impl Fn(&char) -> bool + Copy) -> String {
         let mut chars: Vec<char> = Vec::new();
         let mut current_pos = self.current_span().start;
-        while self.nth_peek(current_pos).map(|x| f(&x)).unwrap_or(false) {
-            chars.push(self.nth_peek(current_pos).unwrap());
-            current_pos += 1;
+        while let Some(ch) = self.nth_peek(current_pos).filter(f) {
+            chars.push(ch);
+            current_pos += ch.len_utf8();
         }
         chars.iter().collect()
     }
 
     /// Dynamically peeks until with last chec and checks
     pub fn checked_lookforward(&mut self, ch: char) -> bool {
+        self.lookforward() == Some(ch)
+    }
+
+    /// Peeks at the first character following the current span that is not an ASCII whitespace
+    pub fn lookforward(&self) -> Option<char> {
         let mut current_pos = self.current_span().end;
         while self.nth_peek(current_pos).map(|c| c.is_ascii_whitespace()).unwrap_or(false) {
             current_pos += 1;
         }
-        self.nth_peek(current_pos).map(|x| x == ch).unwrap_or(false)
+        self.nth_peek(current_pos)
     }
 
-    /// Try to peek at the nth character from the source
-    pub fn nth_peek(&mut self, n: usize) -> Option<char> {
-        self.reference_chars.clone().nth(n)
+    /// Try to peek at the character starting at the nth byte of the source
+    pub fn nth_peek(&self, n: usize) -> Option<char> {
+        self.source.source.get(n..)?.chars().next()
     }
 
     /// Try to peek at next n characters from the source
@@ -208,12 +336,12 @@ impl<'a> Lexer<'a> {
         if cur_span.end + n > self.source.source.len() {
             return String::default()
         }
-        self.source.source[cur_span.start..cur_span.end + n].to_string()
+        self.source.source.get(cur_span.start..cur_span.end + n).unwrap_or_default().to_string()
     }
 
     /// Peek n chars from a given start point in the source
     pub fn peek_n_chars_from(&mut self, n: usize, from: usize) -> String {
-        self.source.source[Span::new(from..(from + n), None).range().unwrap()].to_string()
+        self.source.source.get(from..(from + n)).unwrap_or_default().to_string()
     }
 
     /// Gets the current slice of the source code covered by span
@@ -224,7 +352,7 @@ impl<'a> Lexer<'a> {
     /// Consumes the characters
     pub fn consume(&mut self) -> Option<char> {
         self.chars.next().map(|x| {
-            self.current_span_mut().end += 1;
+            self.current_span_mut().end += x.len_utf8();
             x
         })
     }
@@ -244,8 +372,7 @@ impl<'a> Lexer<'a> {
             if word == peeked {
                 break
             }
-            self.consume();
-            current_pos += 1;
+            current_pos += self.consume().map_or(0, char::len_utf8);
         }
     }
 
@@ -264,6 +391,80 @@ impl<'a> Lexer<'a> {
         exclusive_span.start = exclusive_span.end;
     }
 
+    /// Restarts lexing at the given byte offset of the source.
+    ///
+    /// The offset is clamped to the source length, then to the preceding char boundary. The
+    /// context and lookback are kept, so the offset should be a token boundary lexed in the
+    /// current context.
+    pub fn reset_to(&mut self, offset: usize) {
+        let source = self.source.source;
+        let mut offset = offset.min(source.len());
+        while !source.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        self.chars = source[offset..].chars().peekable();
+        *self.current_span_mut() = Span::new(offset..offset, None);
+        self.previous_end = offset;
+        self.eof = false;
+        self.eof_returned = false;
+        self.inactive_region_pending = false;
+    }
+
+    /// Records a warning.
+    pub fn warn(&mut self, kind: LexicalWarningKind, span: Span) {
+        tracing::warn!(target: "lexer", "{:?} AT {:?}", kind, span);
+        self.warnings.push(LexicalWarning::new(kind, span));
+    }
+
+    /// Gets the current span relative to the file it is in
+    pub fn relative_span(&self) -> Span {
+        self.source
+            .relative_span(self.current_span())
+            .unwrap_or_else(|| self.current_span().clone())
+    }
+
+    /// Converts a range of the flattened source into a span relative to the file of the current
+    /// span, the same way token spans are. The range is clamped to the start of that file.
+    pub fn relative_span_of(&self, range: Range<usize>) -> Span {
+        let current = self.relative_span();
+        let shift = self.current_span().start - current.start;
+        Span::new(range.start.max(shift) - shift..range.end.max(shift) - shift, current.file)
+    }
+
+    /// Checks if the next non-whitespace characters are an `eq` opcode
+    pub fn followed_by_eq(&self) -> bool {
+        let rest = self.source.source.get(self.current_span().end..).unwrap_or_default();
+        let rest = rest.trim_start();
+        rest.starts_with("eq") && !rest[2..].starts_with(|c: char| c.is_alphanumeric() || c == '_')
+    }
+
+    /// Checks the invariants of the current lexing span. Compiled out in release builds.
+    ///
+    /// The span must be ordered, lie within the source, and start where the previous span ended
+    /// (hex literal spans start after their `0x` prefix).
+    pub fn debug_assert_span(&self) {
+        let span = self.current_span();
+        debug_assert!(span.start <= span.end, "Span start {} after end {}", span.start, span.end);
+        debug_assert!(
+            span.end <= self.source.source.len(),
+            "Span end {} past source length {}",
+            span.end,
+            self.source.source.len()
+        );
+        debug_assert!(
+            span.start == self.previous_end ||
+                (span.start == self.previous_end + 2 &&
+                    matches!(
+                        self.source.source.get(self.previous_end..span.start),
+                        Some("0x" | "0X")
+                    )),
+            "Span start {} does not follow previous end {}",
+            span.start,
+            self.previous_end
+        );
+    }
+
     /// Check if a given keyword follows the keyword rules in the `source`. If not, it is a
     /// `TokenKind::Ident`.
     ///
@@ -305,17 +506,362 @@ impl<'a> Lexer<'a> {
                 }
                 false
             }
-            Some(TokenKind::Takes) => self.checked_lookback(TokenKind::Assign),
+            // A modifier may sit between the `=` and `takes`
+            Some(TokenKind::Takes) => {
+                self.checked_lookback(TokenKind::Assign) ||
+                    matches!(
+                        self.last_significant.as_ref().map(|t| &t.kind),
+                        Some(TokenKind::Modifier(_))
+                    )
+            }
             Some(TokenKind::Returns) => {
                 let cur_span_end = self.current_span().end;
-                // Allow for loose and tight syntax (e.g. `returns   (0)`, `returns(0)`, ...)
-                self.checked_lookforward('(') &&
+                // Allow for loose and tight syntax (e.g. `returns   (0)`, `returns(0)`, ...), as
+                // well as a paren-less count in a macro signature (e.g. `returns 1`)
+                let paren_less = self.context == Context::MacroDefinition &&
+                    self.lookforward().is_some_and(|c| c.is_ascii_digit());
+                (self.checked_lookforward('(') || paren_less) &&
                     !self.checked_lookback(TokenKind::Function) &&
                     self.peek_n_chars_from(1, cur_span_end) != ":"
             }
             _ => true,
         }
     }
+
+    /// Checks that the decorator opened by the `#[` at the current span is closed by a `]`.
+    ///
+    /// Nested parentheses and string literals in the decorator arguments are skipped over. The
+    /// decorator is considered unclosed if the source ends, or another `#` is found, before its
+    /// closing bracket.
+    pub fn decorator_is_closed(&self) -> bool {
+        let start = self.current_span().end + 1;
+        let mut depth = 0usize;
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        for ch in self.source.source.get(start..).unwrap_or_default().chars() {
+            match (quote, ch) {
+                (Some(_), _) if escaped => escaped = false,
+                (Some(_), '\\') => escaped = true,
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '"' | '\'') => quote = Some(ch),
+                (None, '(') => depth += 1,
+                (None, ')') => depth = depth.saturating_sub(1),
+                (None, ']') if depth == 0 => return true,
+                (None, '#') => return false,
+                _ => {}
+            }
+        }
+        false
+    }
+
+    /// Warns, in strict mode, if the radix prefix covered by the current span (e.g. `0X`) is
+    /// uppercase.
+    pub fn check_radix_prefix(&mut self) {
+        if self.strict && self.source_slice().ends_with(|c: char| c.is_ascii_uppercase()) {
+            self.warn(LexicalWarningKind::UppercaseRadixPrefix, self.relative_span());
+        }
+    }
+
+    /// Whether the lexer is between a macro's signature and its body, past the `=`.
+    fn in_modifier_position(&self) -> bool {
+        self.context == Context::MacroDefinition &&
+            matches!(
+                self.last_significant.as_ref().map(|t| &t.kind),
+                Some(TokenKind::Assign | TokenKind::CloseParen | TokenKind::Modifier(_))
+            )
+    }
+
+    /// Warns, in strict mode, if the numeric literal covered by the current span is directly
+    /// followed by an identifier character (e.g. `0x10xyz`), pointing at that character.
+    pub fn check_literal_boundary(&mut self) {
+        let next = match self.peek() {
+            Some(next) if self.strict && (self.ident_continue)(next) => next,
+            _ => return,
+        };
+        let literal = self.relative_span();
+        let boundary = Span::new(literal.end..literal.end + next.len_utf8(), literal.file);
+        self.warn(LexicalWarningKind::AmbiguousLiteral, boundary);
+    }
+
+    /// Checks if the current span directly follows a numeric literal or is directly followed by a
+    /// digit.
+    pub fn adjacent_to_literal(&mut self) -> bool {
+        let follows_literal = self.previous_end == self.current_span().start &&
+            matches!(
+                self.lookback.as_ref().map(|t| &t.kind),
+                Some(TokenKind::Literal(_) | TokenKind::Num(_))
+            );
+        follows_literal || self.peek().is_some_and(|c| c.is_ascii_hexdigit())
+    }
+
+    /// Gets the current slice of the source code covered by span, borrowed from the source
+    pub fn source_slice(&self) -> &'a str {
+        let source: &'a str = self.source.source;
+        let span = self.current_span();
+        &source[span.start..span.end]
+    }
+
+    /// Gets the source of the token covered by the current span as written, including the `0x`
+    /// prefix that hex literal spans exclude
+    pub fn written_slice(&self) -> &'a str {
+        let source: &'a str = self.source.source;
+        let span = self.current_span();
+        let prefixed = matches!(self.lookback, Some(Token { kind: TokenKind::Literal(_), .. })) &&
+            span.start
+                .checked_sub(2)
+                .and_then(|start| source.get(start..span.start))
+                .is_some_and(|prefix| prefix.eq_ignore_ascii_case("0x"));
+        &source[if prefixed { span.start - 2 } else { span.start }..span.end]
+    }
+
+    /// Builds an `UnexpectedToken` error for the token covered by the current span, quoting it
+    /// as written in the source
+    pub fn unexpected_token(&self) -> LexicalError<'a> {
+        LexicalError::new(
+            LexicalErrorKind::UnexpectedToken(self.written_slice()),
+            self.current_span().clone(),
+        )
+    }
+
+    /// Lexes the next token that is neither a whitespace nor a comment.
+    ///
+    /// Reaching the end of the source is reported as an `UnexpectedEof` error.
+    pub fn next_significant(&mut self) -> Result<Token, LexicalError<'a>> {
+        loop {
+            match self.next() {
+                Some(Ok(Token { kind: TokenKind::Whitespace | TokenKind::Comment(_), .. })) => {}
+                Some(Ok(Token { kind: TokenKind::Eof, .. })) | None => {
+                    return Err(LexicalError::new(
+                        LexicalErrorKind::UnexpectedEof,
+                        self.current_span().clone(),
+                    ))
+                }
+                Some(res) => return res,
+            }
+        }
+    }
+
+    /// Lexes an inline bytecode block (e.g. `[0x60 0x01]`) into its raw bytes.
+    ///
+    /// Every token between the brackets must be a hex literal. Each literal contributes its bytes
+    /// as written, odd-length literals being padded with a leading zero.
+    pub fn parse_bytecode_block(&mut self) -> Result<Vec<u8>, LexicalError<'a>> {
+        if self.next_significant()?.kind != TokenKind::OpenBracket {
+            return Err(self.unexpected_token())
+        }
+
+        let mut bytes = vec![];
+        loop {
+            match self.next_significant()?.kind {
+                TokenKind::CloseBracket => return Ok(bytes),
+                TokenKind::Literal(_) => {
+                    match str_to_vec(&format_even_bytes(self.source_slice().to_string())) {
+                        Ok(mut literal_bytes) => bytes.append(&mut literal_bytes),
+                        // `true` and `false` are lexed as literals too
+                        Err(_) => return Err(self.unexpected_token()),
+                    }
+                }
+                _ => return Err(self.unexpected_token()),
+            }
+        }
+    }
+
+    /// Lexes a bracketed constant reference (e.g. `[MY_CONSTANT]`) into the constant's name.
+    ///
+    /// Whitespace and comments are tolerated around the name, but the brackets must contain a
+    /// single identifier.
+    pub fn parse_constant_ref(&mut self) -> Result<&'a str, LexicalError<'a>> {
+        if self.next_significant()?.kind != TokenKind::OpenBracket {
+            return Err(self.unexpected_token())
+        }
+        let name = match self.next_significant()?.kind {
+            TokenKind::Ident(_) => self.source_slice(),
+            _ => return Err(self.unexpected_token()),
+        };
+        if self.next_significant()?.kind != TokenKind::CloseBracket {
+            return Err(self.unexpected_token())
+        }
+        Ok(name)
+    }
+
+    /// Lexes an `emit EVENT(args)` statement, capturing the event name and the span of its
+    /// arguments.
+    ///
+    /// `emit` is not a keyword: if the next token isn't `emit` followed by an identifier and
+    /// parenthesized arguments, the lexer is left untouched and `None` is returned so the tokens
+    /// can be lexed as usual. The lexer is left untouched as well when the arguments are
+    /// unterminated, in which case the error is returned.
+    pub fn parse_emit(&mut self) -> Result<Option<EmitStmt<'a>>, LexicalError<'a>> {
+        let checkpoint = self.clone();
+        match self.next_significant() {
+            Ok(Token { kind: TokenKind::Ident(ident), .. }) if ident == "emit" => {}
+            _ => {
+                *self = checkpoint;
+                return Ok(None)
+            }
+        }
+
+        let name = match self.next_significant() {
+            Ok(Token { kind: TokenKind::Ident(_), .. }) => self.source_slice(),
+            _ => {
+                *self = checkpoint;
+                return Ok(None)
+            }
+        };
+        if !matches!(self.next_significant(), Ok(Token { kind: TokenKind::OpenParen, .. })) {
+            *self = checkpoint;
+            return Ok(None)
+        }
+
+        let start = self.current_span().end;
+        let mut depth = 0usize;
+        loop {
+            let kind = match self.next_significant() {
+                Ok(token) => token.kind,
+                Err(e) => {
+                    *self = checkpoint;
+                    return Err(e)
+                }
+            };
+            match kind {
+                TokenKind::OpenParen => depth += 1,
+                TokenKind::CloseParen if depth == 0 => break,
+                TokenKind::CloseParen => depth -= 1,
+                _ => {}
+            }
+        }
+        let args = self.relative_span_of(start..self.current_span().start);
+        Ok(Some(EmitStmt { name, args }))
+    }
+
+    /// Lexes a builtin function call whose single argument is an identifier, such as
+    /// `__codesize(MAIN)` or `__tablestart(TABLE)`.
+    ///
+    /// Builtins are only lexed in a macro body.
+    pub fn parse_builtin_call(&mut self) -> Result<BuiltinCall<'a>, LexicalError<'a>> {
+        let (kind, name) = match self.next_significant()?.kind {
+            TokenKind::BuiltinFunction(builtin) => {
+                (BuiltinFunctionKind::from(builtin), self.source_slice())
+            }
+            _ => return Err(self.unexpected_token()),
+        };
+        if self.next_significant()?.kind != TokenKind::OpenParen {
+            return Err(self.unexpected_token())
+        }
+        let arg = match self.next_significant()?.kind {
+            TokenKind::Ident(_) => self.source_slice(),
+            _ => return Err(self.unexpected_token()),
+        };
+        if self.next_significant()?.kind != TokenKind::CloseParen {
+            return Err(self.unexpected_token())
+        }
+        Ok(BuiltinCall { kind, name, arg })
+    }
+
+    /// Lexes the `takes (N) returns (M)` stack annotations of a macro signature.
+    ///
+    /// The annotations may appear in either order and either may be omitted, though at least one
+    /// is required. Lexing stops after the last annotation, leaving any following token unlexed.
+    pub fn parse_stack_annotation(
+        &mut self,
+    ) -> Result<(Option<usize>, Option<usize>), LexicalError<'a>> {
+        let mut annotations = (None, None);
+        loop {
+            let checkpoint = self.clone();
+            let kind = match self.next_significant() {
+                Ok(token) => token.kind,
+                Err(e) if annotations == (None, None) => return Err(e),
+                Err(_) => {
+                    *self = checkpoint;
+                    break
+                }
+            };
+
+            // `takes` and `returns` are only lexed as keywords in a full macro signature
+            let annotation = match kind {
+                TokenKind::Takes => &mut annotations.0,
+                TokenKind::Returns => &mut annotations.1,
+                TokenKind::Ident(ident) if ident == "takes" => &mut annotations.0,
+                TokenKind::Ident(ident) if ident == "returns" => &mut annotations.1,
+                _ if annotations == (None, None) => return Err(self.unexpected_token()),
+                _ => {
+                    *self = checkpoint;
+                    break
+                }
+            };
+            if annotation.is_some() {
+                return Err(self.unexpected_token())
+            }
+            *annotation = Some(self.parse_stack_count()?);
+
+            if annotations.0.is_some() && annotations.1.is_some() {
+                break
+            }
+        }
+        Ok(annotations)
+    }
+
+    /// Lexes the item count of a `takes` or `returns` stack annotation, either parenthesized
+    /// (`returns (1)`) or bare (`returns 1`).
+    ///
+    /// Note that the Huff parser only accepts the parenthesized form.
+    pub fn parse_stack_count(&mut self) -> Result<usize, LexicalError<'a>> {
+        match self.next_significant()?.kind {
+            TokenKind::Num(count) => return Ok(count),
+            TokenKind::OpenParen => {}
+            _ => return Err(self.unexpected_token()),
+        }
+        let count = match self.next_significant()?.kind {
+            TokenKind::Num(count) => count,
+            _ => return Err(self.unexpected_token()),
+        };
+        if self.next_significant()?.kind != TokenKind::CloseParen {
+            return Err(self.unexpected_token())
+        }
+        Ok(count)
+    }
+
+    /// Lexes the source until a token matching the predicate is produced.
+    ///
+    /// Lexing stops at the first match, so any lexical error occurring after it is not reported.
+    pub fn contains(self, pred: impl Fn(&TokenKind) -> bool) -> Result<bool, LexicalError<'a>> {
+        for token in self {
+            if pred(&token?.kind) {
+                return Ok(true)
+            }
+        }
+        Ok(false)
+    }
+
+    /// Lexes the whole source into tokens that outlive it, including the final `Eof`.
+    pub fn into_owned_tokens(self) -> Result<Vec<OwnedToken>, LexicalError<'a>> {
+        self.map(|token| token.map(|t| t.to_owned_token())).collect()
+    }
+
+    /// Lexes the source into its significant tokens, each paired with the span of the whitespace
+    /// and comments immediately preceding it.
+    ///
+    /// The trivia span is empty, positioned at the end of the previous token, when nothing
+    /// separates the token from its predecessor. The final `Eof` token carries any trailing trivia.
+    pub fn tokens_with_leading_trivia_span(
+        mut self,
+    ) -> impl Iterator<Item = Result<(Token, Span), LexicalError<'a>>> {
+        std::iter::from_fn(move || {
+            let trivia_start = self.previous_end;
+            loop {
+                let trivia_end = self.previous_end;
+                match self.next()? {
+                    Ok(Token { kind: TokenKind::Whitespace | TokenKind::Comment(_), .. }) => {}
+                    Ok(token) => {
+                        let trivia = self.relative_span_of(trivia_start..trivia_end);
+                        return Some(Ok((token, trivia)))
+                    }
+                    Err(e) => return Some(Err(e)),
+                }
+            }
+        })
+    }
 }
 
 impl<'a> Iterator for Lexer<'a> {
@@ -323,7 +869,27 @@ impl<'a> Iterator for Lexer<'a> {
 
     /// Iterates over the source code
     fn next(&mut self) -> Option<Self::Item> {
+        let item = self.next_token();
+        if let Some(Ok(token)) = &item {
+            if !matches!(token.kind, TokenKind::Whitespace | TokenKind::Comment(_) | TokenKind::Eof)
+            {
+                self.last_significant = Some(token.clone());
+            }
+        }
+        self.debug_assert_span();
+        let end = self.current_span().end;
+        self.previous_end = end;
+        item
+    }
+}
+
+impl<'a> Lexer<'a> {
+    /// Lexes the next token, or error, from the source code
+    fn next_token(&mut self) -> Option<Result<Token, LexicalError<'a>>> {
         self.reset();
+        if std::mem::take(&mut self.inactive_region_pending) && self.skip_inactive_region() {
+            return Some(Ok(self.emit(TokenKind::InactiveRegion)))
+        }
         if let Some(ch) = self.consume() {
             let kind = match ch {
                 // Comments
@@ -367,7 +933,31 @@ impl<'a> Iterator for Lexer<'a> {
 
                     if let Some(kind) = &found_kind {
                         kind.clone()
+                    } else if self.defines.is_some() && self.directive_at_span("#ifdef") {
+                        self.nconsume(5);
+                        self.dyn_consume(|c| matches!(c, ' ' | '\t'));
+                        let name_start = self.current_span().end;
+                        self.dyn_consume(|c| c.is_alphanumeric() || c.eq(&'_'));
+                        let name =
+                            self.slice()[name_start - self.current_span().start..].to_string();
+                        if name.is_empty() {
+                            tracing::error!(target: "lexer", "MISSING #ifdef SYMBOL");
+                            return Some(Err(self.unexpected_token()))
+                        }
+                        self.inactive_region_pending =
+                            !self.defines.as_ref().is_some_and(|d| d.contains(name.as_str()));
+                        TokenKind::IfDef(name)
+                    } else if self.defines.is_some() && self.directive_at_span("#endif") {
+                        self.nconsume(5);
+                        TokenKind::EndIf
                     } else if self.context == Context::Global && &self.peek_n_chars(1) == "#[" {
+                        if !self.decorator_is_closed() {
+                            tracing::error!(target: "lexer", "UNCLOSED DECORATOR");
+                            return Some(Err(LexicalError::new(
+                                LexicalErrorKind::UnclosedDecorator,
+                                self.current_span().clone(),
+                            )))
+                        }
                         TokenKind::Pound
                     } else {
                         // Otherwise we don't support # prefixed indentifiers
@@ -379,7 +969,7 @@ impl<'a> Iterator for Lexer<'a> {
                     }
                 }
                 // Alphabetical characters
-                ch if ch.is_alphabetic() || ch.eq(&'_') => {
+                ch if (self.ident_start)(ch) => {
                     let mut found_kind: Option<TokenKind> = None;
 
                     let keys = [
@@ -457,10 +1047,11 @@ impl<'a> Iterator for Lexer<'a> {
                         found_kind = Some(TokenKind::FreeStoragePointer);
                     }
 
+                    let ident_continue = self.ident_continue;
                     let potential_label: String =
-                        self.dyn_peek(|c| c.is_alphanumeric() || c == &'_' || c == &':');
+                        self.dyn_peek(|c| ident_continue(*c) || c == &':');
                     if let true = potential_label.ends_with(':') {
-                        self.dyn_consume(|c| c.is_alphanumeric() || c == &'_');
+                        self.dyn_consume(|c| ident_continue(*c));
                         let label = self.slice();
                         if let Some(l) = label.get(0..label.len()) {
                             found_kind = Some(TokenKind::Label(l.to_string()));
@@ -469,7 +1060,7 @@ impl<'a> Iterator for Lexer<'a> {
                         }
                     }
 
-                    let pot_op = self.dyn_peek(|c| c.is_alphanumeric() || c == &'_');
+                    let pot_op = self.dyn_peek(|c| ident_continue(*c));
 
                     // Syntax sugar: true evaluates to 0x01, false evaluates to 0x00
                     if matches!(pot_op.as_str(), "true" | "false") {
@@ -498,8 +1089,8 @@ impl<'a> Iterator for Lexer<'a> {
                     // Last case ; we are in ABI context and
                     // we are parsing an EVM type
                     if self.context == Context::AbiArgs {
-                        let curr_char = self.peek()?;
-                        if !['(', ')'].contains(&curr_char) {
+                        // Bailing out at the end of the source would skip the `Eof` token
+                        if !matches!(self.peek(), Some('(' | ')')) {
                             self.dyn_consume(|c| c.is_alphanumeric() || *c == '[' || *c == ']');
                             // got a type at this point, we have to know which
                             let raw_type: String = self.slice();
@@ -534,6 +1125,7 @@ impl<'a> Iterator for Lexer<'a> {
                                                             &words[1],
                                                         ),
                                                         span: self.current_span().clone(),
+                                                        hint: None,
                                                     };
                                                     tracing::error!(target: "lexer", "{}", format!("{:?}", err));
                                                     err
@@ -550,6 +1142,7 @@ impl<'a> Iterator for Lexer<'a> {
                                     let err = LexicalError {
                                         kind: LexicalErrorKind::InvalidPrimitiveType(&words[0]),
                                         span: self.current_span().clone(),
+                                        hint: None,
                                     };
                                     tracing::error!(target: "lexer", "{}", format!("{:?}", err));
                                 }
@@ -567,7 +1160,7 @@ impl<'a> Iterator for Lexer<'a> {
                     if let Some(kind) = &found_kind {
                         kind.clone()
                     } else {
-                        self.dyn_consume(|c| c.is_alphanumeric() || c.eq(&'_'));
+                        self.dyn_consume(|c| ident_continue(*c));
 
                         let slice = self.slice();
                         // Check for built-in function calls
@@ -575,27 +1168,48 @@ impl<'a> Iterator for Lexer<'a> {
                             BuiltinFunctionKind::try_from(&slice).is_ok()
                         {
                             TokenKind::BuiltinFunction(slice)
+                        } else if self.in_modifier_position() &&
+                            self.modifiers.contains(slice.as_str())
+                        {
+                            TokenKind::Modifier(slice)
                         } else {
                             TokenKind::Ident(slice)
                         }
                     }
                 }
                 // If it's the start of a hex literal
-                ch if ch == '0' && self.peek().unwrap() == 'x' => {
+                ch if ch == '0' && matches!(self.peek(), Some('x' | 'X')) => {
                     self.consume(); // Consume the 'x' after '0' (separated from the `dyn_consume` so we don't have
                                     // to match `x` in the actual hex)
+                    self.check_radix_prefix();
                     self.dyn_consume(|c| {
                         c.is_numeric() ||
                             // Match a-f & A-F
                             matches!(c, '\u{0041}'..='\u{0046}' | '\u{0061}'..='\u{0066}')
                     });
                     self.current_span_mut().start += 2; // Ignore the "0x"
+                    self.check_literal_boundary();
+
+                    // Literals compared in a function dispatch are expected to be selectors
+                    if self.strict &&
+                        self.context == Context::MacroBody &&
+                        self.followed_by_eq() &&
+                        HexLiteral::new(&self.slice()).is_some_and(|h| !h.is_selector())
+                    {
+                        self.warn(LexicalWarningKind::SuspiciousSelector, self.relative_span());
+                    }
 
                     if self.context == Context::CodeTableBody {
                         // In codetables, the bytecode provided is of arbitrary length. We pass
                         // the code as an Ident, and it is appended to the end of the runtime
                         // bytecode in codegen.
                         TokenKind::Ident(self.slice())
+                    } else if self.source_slice().len() > 64 {
+                        tracing::error!(target: "lexer", "HEX LITERAL WIDER THAN 32 BYTES");
+                        return Some(Err(LexicalError::new(
+                            LexicalErrorKind::OversizedLiteral,
+                            self.current_span().clone(),
+                        )))
                     } else {
                         TokenKind::Literal(str_to_bytes32(self.slice().as_ref()))
                     }
@@ -642,7 +1256,38 @@ impl<'a> Iterator for Lexer<'a> {
                 ',' => TokenKind::Comma,
                 '0'..='9' => {
                     self.dyn_consume(char::is_ascii_digit);
-                    TokenKind::Num(self.slice().parse().unwrap())
+                    let slice = self.slice();
+                    let radix = match self.leading_zero {
+                        _ if slice.len() == 1 || !slice.starts_with('0') => 10,
+                        LeadingZeroPolicy::Decimal => 10,
+                        LeadingZeroPolicy::Octal => 8,
+                        LeadingZeroPolicy::Reject => {
+                            tracing::error!(target: "lexer", "LEADING ZERO IN DECIMAL LITERAL");
+                            return Some(Err(LexicalError::new(
+                                LexicalErrorKind::LeadingZero,
+                                self.current_span().clone(),
+                            )))
+                        }
+                    };
+                    if let Some(ch) = slice.chars().find(|c| !c.is_digit(radix)) {
+                        tracing::error!(target: "lexer", "INVALID DIGIT '{}' IN LITERAL", ch);
+                        return Some(Err(LexicalError::new(
+                            LexicalErrorKind::InvalidCharacter(ch),
+                            self.current_span().clone(),
+                        )))
+                    }
+                    let num = match usize::from_str_radix(&slice, radix) {
+                        Ok(num) => num,
+                        Err(_) => {
+                            tracing::error!(target: "lexer", "NUMERIC LITERAL OVERFLOW");
+                            return Some(Err(LexicalError::new(
+                                LexicalErrorKind::NumOverflow,
+                                self.current_span().clone(),
+                            )))
+                        }
+                    };
+                    self.check_literal_boundary();
+                    TokenKind::Num(num)
                 }
                 // Lexes Spaces and Newlines as Whitespace
                 ch if ch.is_ascii_whitespace() => {
@@ -661,14 +1306,7 @@ impl<'a> Iterator for Lexer<'a> {
                             self.consume();
                         }
                         Some(_) => {}
-                        None => {
-                            self.eof = true;
-                            tracing::error!(target: "lexer", "UNEXPECTED EOF SPAN");
-                            return Some(Err(LexicalError::new(
-                                LexicalErrorKind::UnexpectedEof,
-                                self.current_span().clone(),
-                            )))
-                        }
+                        None => return Some(Err(self.unterminated_string('"'))),
                     }
                     self.consume();
                 },
@@ -684,17 +1322,31 @@ impl<'a> Iterator for Lexer<'a> {
                             self.consume();
                         }
                         Some(_) => {}
-                        None => {
-                            self.eof = true;
-                            tracing::error!(target: "lexer", "UNEXPECTED EOF SPAN");
-                            return Some(Err(LexicalError::new(
-                                LexicalErrorKind::UnexpectedEof,
-                                self.current_span().clone(),
-                            )))
-                        }
+                        None => return Some(Err(self.unterminated_string('\''))),
                     }
                     self.consume();
                 },
+                // Non-ASCII whitespace splitting a literal, likely left by a bad copy-paste
+                ch if self.strict &&
+                    (ch.is_whitespace() && !ch.is_ascii() ||
+                        matches!(ch, '\u{200B}'..='\u{200D}' | '\u{2060}' | '\u{FEFF}')) &&
+                    self.adjacent_to_literal() =>
+                {
+                    // Still an invalid character, but explained by the warning and the hint
+                    self.warn(
+                        LexicalWarningKind::SuspiciousWhitespaceInLiteral,
+                        self.relative_span(),
+                    );
+                    return Some(Err(LexicalError {
+                        kind: LexicalErrorKind::InvalidCharacter(ch),
+                        span: self.current_span().clone(),
+                        hint: Some(
+                            "Non-ASCII whitespace next to a numeric literal, likely pasted by \
+                             mistake"
+                                .to_string(),
+                        ),
+                    }))
+                }
                 // At this point, the source code has an invalid or unsupported token
                 ch => {
                     tracing::error!(target: "lexer", "UNSUPPORTED TOKEN '{}'", ch);
@@ -705,25 +1357,7 @@ impl<'a> Iterator for Lexer<'a> {
                 }
             };
 
-            if self.peek().is_none() {
-                self.eof = true;
-            }
-
-            // Produce a relative span
-            let new_span = match self.source.relative_span(self.current_span()) {
-                Some(s) => s,
-                None => {
-                    tracing::warn!(target: "lexer", "UNABLE TO RELATIVIZE SPAN FOR \"{}\"", kind);
-                    tracing::warn!(target: "lexer", "Current Span: {:?}", self.current_span());
-                    self.current_span().clone()
-                }
-            };
-            let token = Token { kind, span: new_span };
-            if token.kind != TokenKind::Whitespace {
-                self.lookback = Some(token.clone());
-            }
-
-            return Some(Ok(token))
+            return Some(Ok(self.emit(kind)))
         }
 
         // Mark EOF
@@ -741,4 +1375,106 @@ impl<'a> Iterator for Lexer<'a> {
 
         None
     }
+
+    /// Builds the `UnexpectedEof` error of a string left open by the given quote.
+    ///
+    /// In strict mode, a lone quote of the other style within the string is pointed out in the
+    /// error hint as the likely intended closing quote.
+    fn unterminated_string(&mut self, quote: char) -> LexicalError<'a> {
+        self.eof = true;
+        tracing::error!(target: "lexer", "UNEXPECTED EOF SPAN");
+        let mut err =
+            LexicalError::new(LexicalErrorKind::UnexpectedEof, self.current_span().clone());
+
+        let other = if quote == '"' { '\'' } else { '"' };
+        let contents = &self.source_slice()[1..];
+        if self.strict && contents.matches(other).count() == 1 {
+            err.hint = Some(format!(
+                "String opened with {quote} contains a mismatched {other}, was it meant to close it?"
+            ));
+        }
+        err
+    }
+
+    /// Produces the token of the given kind covering the current span
+    fn emit(&mut self, kind: TokenKind) -> Token {
+        if self.peek().is_none() {
+            self.eof = true;
+        }
+
+        // Produce a relative span
+        let new_span = match self.source.relative_span(self.current_span()) {
+            Some(s) => s,
+            None => {
+                tracing::warn!(target: "lexer", "UNABLE TO RELATIVIZE SPAN FOR \"{}\"", kind);
+                tracing::warn!(target: "lexer", "Current Span: {:?}", self.current_span());
+                self.current_span().clone()
+            }
+        };
+        let token = Token { kind, span: new_span };
+        if token.kind != TokenKind::Whitespace {
+            self.lookback = Some(token.clone());
+        }
+
+        token
+    }
+
+    /// Whether the source starts with the directive (e.g. `#ifdef`), followed by a character
+    /// that can't continue an identifier
+    fn is_directive(&self, source: &str, directive: &str) -> bool {
+        source.strip_prefix(directive).is_some_and(|rest| !rest.starts_with(self.ident_continue))
+    }
+
+    /// Whether the directive starts at the current span
+    fn directive_at_span(&self, directive: &str) -> bool {
+        let source = self.source.source.get(self.current_span().start..).unwrap_or_default();
+        self.is_directive(source, directive)
+    }
+
+    /// Consumes the source disabled by an `#ifdef`, up to its matching `#endif` or the end of
+    /// the source. Returns whether any character was consumed.
+    ///
+    /// Directives within comments and strings are ignored.
+    fn skip_inactive_region(&mut self) -> bool {
+        let mut depth = 0usize;
+        while self.peek().is_some() {
+            let rest = self.source.source.get(self.current_span().end..).unwrap_or_default();
+            if rest.starts_with("//") {
+                self.dyn_consume(|c| *c != '\n');
+                continue
+            } else if rest.starts_with("/*") {
+                self.nconsume(2);
+                while self.peek().is_some() {
+                    if self.source.source[self.current_span().end..].starts_with("*/") {
+                        self.nconsume(2);
+                        break
+                    }
+                    self.consume();
+                }
+                continue
+            } else if let Some(quote) = rest.chars().next().filter(|c| matches!(c, '"' | '\'')) {
+                self.consume();
+                while let Some(ch) = self.consume() {
+                    match ch {
+                        '\\' => {
+                            self.consume();
+                        }
+                        ch if ch == quote => break,
+                        _ => {}
+                    }
+                }
+                continue
+            } else if self.is_directive(rest, "#ifdef") {
+                depth += 1;
+            } else if self.is_directive(rest, "#endif") {
+                if depth == 0 {
+                    break
+                }
+                depth -= 1;
+            }
+            self.consume();
+        }
+        let span = self.current_span();
+        span.start != span.end
+    }
 }
@@ -0,0 +1,43 @@
+use crate::Lexer;
+use huff_utils::prelude::*;
+
+/// Identifies one of the sources lexed by a [MultiLexer].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SourceId(pub usize);
+
+/// Lexes several sources in turn, tagging every token with the id of its source.
+///
+/// Each source is lexed on its own, so token spans are relative to the start of their source and
+/// every source ends with its own `Eof` token.
+#[derive(Clone)]
+pub struct MultiLexer<'a> {
+    /// The sources remaining to be lexed, in reverse order
+    pending: Vec<(SourceId, &'a str)>,
+    /// The source currently being lexed
+    current: Option<(SourceId, Lexer<'a>)>,
+}
+
+impl<'a> MultiLexer<'a> {
+    /// Public associated function to instantiate a new multi-source lexer.
+    pub fn new(sources: &[(SourceId, &'a str)]) -> Self {
+        Self { pending: sources.iter().rev().copied().collect(), current: None }
+    }
+}
+
+impl<'a> Iterator for MultiLexer<'a> {
+    type Item = (SourceId, Result<Token, LexicalError<'a>>);
+
+    /// Iterates over the tokens of every source
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((id, lexer)) = &mut self.current {
+                if let Some(token) = lexer.next() {
+                    return Some((*id, token))
+                }
+            }
+            let (id, source) = self.pending.pop()?;
+            let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+            self.current = Some((id, Lexer::new(flattened_source)));
+        }
+    }
+}
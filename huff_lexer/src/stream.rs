@@ -0,0 +1,398 @@
+use crate::Lexer;
+use huff_utils::prelude::*;
+use std::collections::BTreeMap;
+
+/// Returns the tokens of the named macro's body, excluding its surrounding braces.
+///
+/// The body is delimited by brace balancing, so nested braces are kept within it. Returns `None`
+/// if no `macro`, `fn`, or `test` of that name is defined or if its body is unterminated.
+pub fn section_tokens<'t>(tokens: &'t [Token], macro_name: &str) -> Option<&'t [Token]> {
+    let significant = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, t)| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Comment(_)))
+        .collect::<Vec<(usize, &Token)>>();
+
+    let definition = significant.windows(2).position(|pair| {
+        matches!(pair[0].1.kind, TokenKind::Macro | TokenKind::Fn | TokenKind::Test) &&
+            matches!(&pair[1].1.kind, TokenKind::Ident(name) if name == macro_name)
+    })?;
+    let open = significant[definition..]
+        .iter()
+        .find(|(_, t)| t.kind == TokenKind::OpenBrace)
+        .map(|(i, _)| *i)?;
+
+    let mut depth = 0usize;
+    for (i, token) in tokens.iter().enumerate().skip(open) {
+        match token.kind {
+            TokenKind::OpenBrace => depth += 1,
+            TokenKind::CloseBrace => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&tokens[open + 1..i])
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Warns about every `#define constant NAME = <literal>` whose literal is wider than 32 bytes.
+///
+/// The width of a hex literal is measured on its source, leading zeros included. The lexer
+/// rejects hex literals wider than 32 bytes with a `LexicalErrorKind::OversizedLiteral` error,
+/// so the source is lexed here, recovering from such errors to measure the rejected literals.
+/// Constants assigned `FREE_STORAGE_POINTER()` are not checked.
+pub fn check_constant_widths(source: &str) -> Vec<LexicalWarning> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    let mut significant = vec![];
+    loop {
+        let start = lexer.previous_end;
+        match lexer.next() {
+            Some(Ok(Token { kind: TokenKind::Whitespace | TokenKind::Comment(_), .. })) => {}
+            Some(Ok(token)) => significant.push(token),
+            Some(Err(e)) if e.kind == LexicalErrorKind::OversizedLiteral => {
+                significant.push(Token::new(TokenKind::Literal([0; 32]), e.span))
+            }
+            // Stop if the error did not consume any source
+            Some(Err(_)) if lexer.previous_end != start => {}
+            _ => break,
+        }
+    }
+
+    significant
+        .windows(5)
+        .filter_map(|window| match window {
+            [define, constant, name, assign, literal]
+                if define.kind == TokenKind::Define &&
+                    constant.kind == TokenKind::Constant &&
+                    matches!(name.kind, TokenKind::Ident(_)) &&
+                    assign.kind == TokenKind::Assign =>
+            {
+                let width = match literal.kind {
+                    // Hex literal spans exclude the "0x" prefix
+                    TokenKind::Literal(_) => {
+                        source[literal.span.start..literal.span.end].len().div_ceil(2)
+                    }
+                    TokenKind::Num(value) => {
+                        (usize::BITS - value.leading_zeros()).div_ceil(8) as usize
+                    }
+                    _ => return None,
+                };
+                (width > 32).then(|| {
+                    LexicalWarning::new(LexicalWarningKind::OversizedConstant, literal.span.clone())
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Lexes the source into the highlighting category of each of its tokens.
+///
+/// Whitespace is omitted. Lexing recovers from errors, the source covered by an error being
+/// reported with the `Error` category.
+pub fn highlight(source: &str) -> Vec<(Span, TokenCategory)> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    let mut spans = vec![];
+    loop {
+        let start = lexer.previous_end;
+        match lexer.next() {
+            Some(Ok(token)) => match token.kind.category() {
+                TokenCategory::Whitespace => {}
+                category => spans.push((token.span, category)),
+            },
+            Some(Err(e)) => {
+                spans.push((e.span, TokenCategory::Error));
+                // Stop if the error did not consume any source
+                if lexer.previous_end == start {
+                    break
+                }
+            }
+            None => break,
+        }
+    }
+    spans
+}
+
+/// Warns about the code following a terminating opcode (`return`, `revert`, `stop`,
+/// `selfdestruct` or `invalid`) in a macro body, up to the next label or the end of the body.
+///
+/// Each unreachable run of tokens produces a single warning spanning it.
+pub fn find_unreachable_code(tokens: &[Token]) -> Vec<LexicalWarning> {
+    let mut warnings = vec![];
+    let mut terminated = false;
+    let mut unreachable: Option<Span> = None;
+    let mut flush = |unreachable: &mut Option<Span>| {
+        if let Some(span) = unreachable.take() {
+            warnings.push(LexicalWarning::new(LexicalWarningKind::UnreachableCode, span));
+        }
+    };
+
+    for token in tokens {
+        match &token.kind {
+            TokenKind::Whitespace | TokenKind::Comment(_) => {}
+            // Labels are jump destinations, and braces delimit macro bodies
+            TokenKind::Label(_) |
+            TokenKind::Opcode(Opcode::Jumpdest) |
+            TokenKind::OpenBrace |
+            TokenKind::CloseBrace |
+            TokenKind::Eof => {
+                terminated = false;
+                flush(&mut unreachable);
+            }
+            _ if terminated => {
+                let span = unreachable.get_or_insert_with(|| token.span.clone());
+                span.end = token.span.end;
+            }
+            TokenKind::Opcode(
+                Opcode::Return |
+                Opcode::Revert |
+                Opcode::Stop |
+                Opcode::Selfdestruct |
+                Opcode::Invalid,
+            ) => terminated = true,
+            _ => {}
+        }
+    }
+    flush(&mut unreachable);
+    warnings
+}
+
+/// A run of line comments on consecutive lines
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CommentBlock {
+    /// The span covering every comment of the block
+    pub span: Span,
+    /// The text of the comments, without their `//` prefix, one per line
+    pub text: String,
+}
+
+/// Groups the line comments on consecutive lines into comment blocks.
+///
+/// A blank line, a block comment or any other token ends the current block.
+pub fn coalesce_comments(tokens: &[Token], source: &str) -> Vec<CommentBlock> {
+    let mut blocks = vec![];
+    let mut current: Option<CommentBlock> = None;
+    for token in tokens {
+        match &token.kind {
+            TokenKind::Comment(comment) if comment.starts_with("//") => {
+                let line = comment[2..].strip_prefix(' ').unwrap_or(&comment[2..]);
+                // Line comments run up to the line feed, so CRLF line endings leave a '\r'
+                let line = line.trim_end_matches('\r');
+                match &mut current {
+                    Some(block) => {
+                        block.span.end = token.span.end;
+                        block.text.push('\n');
+                        block.text.push_str(line);
+                    }
+                    None => {
+                        current =
+                            Some(CommentBlock { span: token.span.clone(), text: line.to_string() })
+                    }
+                }
+            }
+            TokenKind::Whitespace
+                if source
+                    .get(token.span.start..token.span.end)
+                    .is_some_and(|ws| ws.matches('\n').count() <= 1) => {}
+            _ => blocks.extend(current.take()),
+        }
+    }
+    blocks.extend(current);
+    blocks
+}
+
+/// Sums the net stack effect of a straight-line run of opcodes and literals, literals each
+/// pushing one item.
+///
+/// Returns `None` if the run contains any other token, such as a macro call or a label, whose
+/// stack effect cannot be known from the tokens alone.
+pub fn net_stack_effect(tokens: &[Token]) -> Option<isize> {
+    tokens.iter().try_fold(0isize, |effect, token| match &token.kind {
+        TokenKind::Whitespace | TokenKind::Comment(_) => Some(effect),
+        TokenKind::Literal(_) => Some(effect + 1),
+        TokenKind::Opcode(opcode) => {
+            let (pops, pushes) = opcode.stack_io();
+            Some(effect + pushes as isize - pops as isize)
+        }
+        _ => None,
+    })
+}
+
+/// The opcodes used by a token stream, for quick capability detection
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpcodeSummary {
+    /// The number of opcodes
+    pub total: usize,
+    /// The number of opcodes in each category used
+    pub categories: BTreeMap<OpcodeCategory, usize>,
+    /// Whether `selfdestruct` is used
+    pub selfdestruct: bool,
+    /// Whether `delegatecall` is used
+    pub delegatecall: bool,
+    /// Whether `callcode` is used
+    pub callcode: bool,
+    /// Whether `create2` is used
+    pub create2: bool,
+}
+
+/// Summarizes the opcodes used by the tokens, flagging the dangerous ones.
+pub fn opcode_summary(tokens: &[Token]) -> OpcodeSummary {
+    let mut summary = OpcodeSummary::default();
+    for token in tokens {
+        let opcode = match &token.kind {
+            TokenKind::Opcode(opcode) => opcode,
+            _ => continue,
+        };
+        summary.total += 1;
+        *summary.categories.entry(opcode.category()).or_default() += 1;
+        match opcode {
+            Opcode::Selfdestruct => summary.selfdestruct = true,
+            Opcode::Delegatecall => summary.delegatecall = true,
+            Opcode::Callcode => summary.callcode = true,
+            Opcode::Create2 => summary.create2 = true,
+            _ => {}
+        }
+    }
+    summary
+}
+
+/// The characters a line is indented with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentKind {
+    /// The line is not indented, or is blank
+    None,
+    /// The line is indented with spaces only
+    Spaces,
+    /// The line is indented with tabs only
+    Tabs,
+    /// The line is indented with both spaces and tabs
+    Mixed,
+}
+
+/// Reports, for each line of the source, the width of its indentation (in characters) and the
+/// characters it is indented with.
+///
+/// The indentation is read from the whitespace tokens beginning lines. Blank lines are reported
+/// as not indented.
+pub fn indentation_report(tokens: &[Token], source: &str) -> Vec<(usize, IndentKind)> {
+    let line_starts = std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(i, _)| i + 1))
+        .collect::<Vec<usize>>();
+    let mut report = vec![(0, IndentKind::None); line_starts.len()];
+
+    for token in tokens.iter().filter(|t| t.kind == TokenKind::Whitespace) {
+        let whitespace = match source.get(token.span.start..token.span.end) {
+            Some(whitespace) => whitespace,
+            None => continue,
+        };
+        let first_line = line_starts.partition_point(|&start| start <= token.span.start) - 1;
+
+        // Only the first segment of a whitespace token in the middle of a line isn't indentation
+        let skip = usize::from(line_starts[first_line] != token.span.start);
+        let segments = whitespace.split('\n').collect::<Vec<&str>>();
+        for (i, indent) in segments.iter().enumerate().skip(skip) {
+            // The last segment is only blank if it ends the source
+            let blank = i < segments.len() - 1 || token.span.end == source.len();
+            if blank || indent.is_empty() {
+                continue
+            }
+            let kind = match (indent.contains(' '), indent.contains('\t')) {
+                (true, true) => IndentKind::Mixed,
+                (false, true) => IndentKind::Tabs,
+                (true, false) => IndentKind::Spaces,
+                (false, false) => IndentKind::None,
+            };
+            report[first_line + i] = (indent.chars().count(), kind);
+        }
+    }
+    report
+}
+
+/// Lexes the source and collects the path of every `#include`, along with the span of its
+/// string literal.
+///
+/// Unlike `Lexer::lex_imports`, any lexical error in the source is reported.
+pub fn extract_includes(source: &str) -> Result<Vec<(String, Span)>, LexicalError<'_>> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut includes = vec![];
+    let mut after_include = false;
+    for token in Lexer::new(flattened_source) {
+        let token = token?;
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::Comment(_) => continue,
+            TokenKind::Str(path) if after_include => includes.push((path, token.span)),
+            TokenKind::Include => {
+                after_include = true;
+                continue
+            }
+            _ => {}
+        }
+        after_include = false;
+    }
+    Ok(includes)
+}
+
+/// Counts the tokens that are neither a whitespace, a comment, nor the terminal `Eof`.
+pub fn significant_len(tokens: &[Token]) -> usize {
+    tokens
+        .iter()
+        .filter(|t| {
+            !matches!(t.kind, TokenKind::Whitespace | TokenKind::Comment(_) | TokenKind::Eof)
+        })
+        .count()
+}
+
+/// Splits the tokens into one slice per top-level `#define`, each starting with its `Define`
+/// token and ending where the definition does.
+///
+/// A constant ends with its value, and a definition with a body, such as a macro or a table, with
+/// the brace closing it. Braces are balanced, so a `Define` within a body does not split it. Other
+/// definitions, such as events, end with their last token before the next `#define`, `#include`
+/// or the end of the source. The tokens between definitions and the terminal `Eof` are not part
+/// of any slice.
+pub fn split_definitions(tokens: &[Token]) -> Vec<&[Token]> {
+    let mut definitions = vec![];
+    let mut rest = tokens;
+    while let Some(start) = rest.iter().position(|t| t.kind == TokenKind::Define) {
+        let end = start + definition_len(&rest[start..]);
+        definitions.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    definitions
+}
+
+/// Returns the number of tokens of the definition starting with the `Define` token at the start
+/// of the tokens.
+fn definition_len(tokens: &[Token]) -> usize {
+    let significant = tokens
+        .iter()
+        .enumerate()
+        .skip(1)
+        .filter(|(_, t)| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Comment(_)))
+        .collect::<Vec<(usize, &Token)>>();
+    let constant = significant.first().is_some_and(|(_, t)| t.kind == TokenKind::Constant);
+
+    let mut len = 1;
+    let mut depth = 0usize;
+    let mut assigned = false;
+    for (i, token) in significant {
+        match token.kind {
+            TokenKind::Eof => break,
+            TokenKind::Define | TokenKind::Include if depth == 0 => break,
+            TokenKind::OpenBrace => depth += 1,
+            TokenKind::CloseBrace if depth <= 1 => return i + 1,
+            TokenKind::CloseBrace => depth -= 1,
+            // The value of a constant is the token following its `=`
+            _ if constant && assigned => return i + 1,
+            TokenKind::Assign => assigned = true,
+            _ => {}
+        }
+        len = i + 1;
+    }
+    len
+}
@@ -0,0 +1,48 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn builds_lexer_with_options() {
+    let source = "#define macro MAIN() = unchecked { 0X10 0123 }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::builder()
+        .strict(true)
+        .leading_zero(LeadingZeroPolicy::Octal)
+        .modifiers(["unchecked"])
+        .build(flattened_source);
+
+    let kinds = lexer.by_ref().map(|x| x.unwrap().kind).collect::<Vec<TokenKind>>();
+    assert!(kinds.contains(&TokenKind::Modifier("unchecked".to_string())));
+    assert!(kinds.contains(&TokenKind::Num(0o123)));
+    assert_eq!(
+        lexer.warnings,
+        vec![LexicalWarning::new(
+            LexicalWarningKind::UppercaseRadixPrefix,
+            Span::new(35..37, None)
+        )]
+    );
+}
+
+#[test]
+fn default_builder_matches_new() {
+    let source = "#define macro MAIN() = unchecked { 0X10 0123 }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let built = LexerBuilder::new().build(flattened_source.clone());
+    let lexer = Lexer::new(flattened_source);
+
+    assert_eq!((built.strict, built.leading_zero), (lexer.strict, lexer.leading_zero));
+    assert_eq!((built.defines.is_none(), built.modifiers.is_empty()), (true, true));
+    let built = built.map(|x| x.unwrap()).collect::<Vec<Token>>();
+    assert_eq!(built, lexer.map(|x| x.unwrap()).collect::<Vec<Token>>());
+}
+
+#[test]
+fn builds_lexer_with_defines() {
+    let source = "#ifdef A\n0x01\n#endif";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = LexerBuilder::new().defines(["A"]).build(flattened_source);
+
+    let kinds = lexer.map(|x| x.unwrap().kind).collect::<Vec<TokenKind>>();
+    assert_eq!(kinds[0], TokenKind::IfDef("A".to_string()));
+    assert!(kinds.contains(&TokenKind::Literal(str_to_bytes32("01"))));
+}
@@ -1,5 +1,7 @@
-use huff_lexer::Lexer;
-use huff_utils::prelude::{FullFileSource, Span, Token, TokenKind};
+use huff_lexer::{BuiltinCall, Context, Lexer};
+use huff_utils::prelude::{
+    BuiltinFunctionKind, FullFileSource, LexicalErrorKind, Span, Token, TokenKind,
+};
 use std::ops::Deref;
 
 #[test]
@@ -178,3 +180,29 @@ fn fails_to_parse_invalid_builtin() {
         assert!(lexer.eof);
     }
 }
+
+#[test]
+fn parses_builtin_call() {
+    for (source, kind, name, arg) in [
+        ("__codesize(MAIN)", BuiltinFunctionKind::Codesize, "__codesize", "MAIN"),
+        ("__tablestart( FOO )", BuiltinFunctionKind::Tablestart, "__tablestart", "FOO"),
+    ] {
+        let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+        let mut lexer = Lexer::new(flattened_source);
+        lexer.context = Context::MacroBody;
+
+        assert_eq!(lexer.parse_builtin_call().unwrap(), BuiltinCall { kind, name, arg });
+    }
+}
+
+#[test]
+fn fails_on_non_identifier_builtin_argument() {
+    let source = "__codesize(0x10)";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.context = Context::MacroBody;
+
+    let err = lexer.parse_builtin_call().unwrap_err();
+    assert_eq!(err.kind, LexicalErrorKind::UnexpectedToken("0x10"));
+    assert_eq!(err.span, Span::new(13..15, None));
+}
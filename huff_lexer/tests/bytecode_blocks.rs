@@ -0,0 +1,66 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn parses_bytecode_block() {
+    let source = "[0x60 0x01]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_bytecode_block().unwrap(), vec![0x60, 0x01]);
+}
+
+#[test]
+fn parses_multi_byte_literals_in_block() {
+    let source = "[ 0x6001 0x1 ]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_bytecode_block().unwrap(), vec![0x60, 0x01, 0x01]);
+}
+
+#[test]
+fn parses_empty_bytecode_block() {
+    let source = "[]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_bytecode_block().unwrap(), Vec::<u8>::new());
+}
+
+#[test]
+fn fails_on_identifier_in_bytecode_block() {
+    let source = "[0x60 foo]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let err = lexer.parse_bytecode_block().unwrap_err();
+    assert_eq!(
+        err,
+        LexicalError::new(LexicalErrorKind::UnexpectedToken("foo"), Span::new(6..9, None))
+    );
+}
+
+#[test]
+fn fails_on_unterminated_bytecode_block() {
+    let source = "[0x60";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let err = lexer.parse_bytecode_block().unwrap_err();
+    assert_eq!(err.kind, LexicalErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn quotes_hex_literal_as_written_in_error() {
+    let source = "0x60 0x01";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    // The message includes the `0x` prefix that the literal's span excludes
+    let err = lexer.parse_bytecode_block().unwrap_err();
+    assert_eq!(
+        err,
+        LexicalError::new(LexicalErrorKind::UnexpectedToken("0x60"), Span::new(2..4, None))
+    );
+}
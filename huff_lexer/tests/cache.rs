@@ -0,0 +1,29 @@
+#![cfg(feature = "cache")]
+
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn round_trips_token_stream() {
+    let source = r#"
+    /* Storage */
+    #define constant OWNER = FREE_STORAGE_POINTER()
+
+    #define macro MAIN() = takes(0) returns(0) {
+        0x00 calldataload 0xE0 shr
+        __FUNC_SIG("owner()") eq owner jumpi
+        owner:
+            [OWNER] sload 0x00 mstore
+            0x20 0x00 return
+    }
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let tokens = Lexer::new(flattened_source).map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    let mut bytes = vec![];
+    serialize_tokens(&tokens, 0xdead_beef, &mut bytes).unwrap();
+    let (source_hash, cached) = deserialize_tokens(bytes.as_slice()).unwrap();
+
+    assert_eq!(source_hash, 0xdead_beef);
+    assert_eq!(cached, tokens);
+}
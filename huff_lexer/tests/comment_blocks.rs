@@ -0,0 +1,46 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+fn lex(source: &str) -> Vec<Token> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    Lexer::new(flattened_source).map(|x| x.unwrap()).collect()
+}
+
+#[test]
+fn coalesces_consecutive_line_comments() {
+    let source = "// Transfers tokens\n//\n// Reverts on insufficient balance\n#define macro TRANSFER() = takes(0) returns(0) {}";
+    let end = source.find("\n#define").unwrap();
+
+    assert_eq!(
+        coalesce_comments(&lex(source), source),
+        vec![CommentBlock {
+            span: Span::new(0..end, None),
+            text: "Transfers tokens\n\nReverts on insufficient balance".to_string()
+        }]
+    );
+}
+
+#[test]
+fn separates_comment_groups() {
+    let source = "// First\n// group\n#define constant A = 0x01\n    // Second group\n";
+    let blocks = coalesce_comments(&lex(source), source);
+
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].text, "First\ngroup");
+    assert_eq!(blocks[0].span, Span::new(0..17, None));
+    assert_eq!(blocks[1].text, "Second group");
+
+    // A blank line also separates groups
+    let source = "// First\n\n// Second\n";
+    assert_eq!(coalesce_comments(&lex(source), source).len(), 2);
+}
+
+#[test]
+fn trims_carriage_returns_from_crlf_lines() {
+    let source = "// First\r\n// second\r\n\r\n// Third\r\n";
+    let blocks = coalesce_comments(&lex(source), source);
+
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0].text, "First\nsecond");
+    assert_eq!(blocks[1].text, "Third");
+}
@@ -0,0 +1,112 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+use std::collections::HashSet;
+
+const SOURCE: &str =
+    "#ifdef DEBUG\n#define constant FOO = 0x01\n#endif\n#define constant BAR = 0x02";
+
+fn lex(defines: &[&'static str]) -> Vec<Token> {
+    let flattened_source = FullFileSource { source: SOURCE, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.defines = Some(defines.iter().copied().collect::<HashSet<&str>>());
+    lexer
+        .map(|x| x.unwrap())
+        .filter(|x| !matches!(x.kind, TokenKind::Whitespace))
+        .collect::<Vec<Token>>()
+}
+
+#[test]
+fn lexes_defined_region_as_active() {
+    let tokens = lex(&["DEBUG"]);
+
+    assert_eq!(
+        tokens[0],
+        Token::new(TokenKind::IfDef("DEBUG".to_string()), Span::new(0..12, None))
+    );
+    assert!(!tokens.iter().any(|t| t.kind == TokenKind::InactiveRegion));
+    assert_eq!(tokens[1].kind, TokenKind::Define);
+    assert_eq!(tokens[3].kind, TokenKind::Ident("FOO".to_string()));
+    assert_eq!(tokens[6], Token::new(TokenKind::EndIf, Span::new(41..47, None)));
+    assert_eq!(tokens[8].kind, TokenKind::Constant);
+    assert_eq!(tokens[9].kind, TokenKind::Ident("BAR".to_string()));
+}
+
+#[test]
+fn lexes_undefined_region_as_inactive() {
+    let tokens = lex(&["RELEASE"]);
+
+    assert_eq!(
+        tokens[0],
+        Token::new(TokenKind::IfDef("DEBUG".to_string()), Span::new(0..12, None))
+    );
+    // The disabled definition is a single token, trailing newline included
+    assert_eq!(tokens[1], Token::new(TokenKind::InactiveRegion, Span::new(12..41, None)));
+    assert_eq!(&SOURCE[12..41], "\n#define constant FOO = 0x01\n");
+    assert_eq!(tokens[2], Token::new(TokenKind::EndIf, Span::new(41..47, None)));
+
+    // Lexing resumes after the `#endif`
+    assert_eq!(tokens[3].kind, TokenKind::Define);
+    assert_eq!(tokens[4].kind, TokenKind::Constant);
+    assert_eq!(tokens[5].kind, TokenKind::Ident("BAR".to_string()));
+}
+
+#[test]
+fn skips_nested_regions() {
+    let source = "#ifdef A\n#ifdef B\n0x01\n#endif\n#endif 0x02";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.defines = Some(HashSet::from(["B"]));
+    let tokens = lexer
+        .map(|x| x.unwrap().kind)
+        .filter(|x| !matches!(x, TokenKind::Whitespace))
+        .collect::<Vec<TokenKind>>();
+
+    assert_eq!(
+        tokens,
+        vec![
+            TokenKind::IfDef("A".to_string()),
+            TokenKind::InactiveRegion,
+            TokenKind::EndIf,
+            TokenKind::Literal(str_to_bytes32("02")),
+            TokenKind::Eof,
+        ]
+    );
+}
+
+#[test]
+fn directives_require_defines() {
+    let flattened_source = FullFileSource { source: SOURCE, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(
+        lexer.next().unwrap().unwrap_err(),
+        LexicalError::new(LexicalErrorKind::InvalidCharacter('#'), Span::new(0..1, None))
+    );
+}
+
+#[test]
+fn directives_require_a_word_boundary() {
+    let source = "#ifdefFOO #endifx";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.defines = Some(HashSet::from(["FOO"]));
+    let kinds = lexer.filter_map(|x| x.ok()).map(|t| t.kind).collect::<Vec<TokenKind>>();
+
+    assert!(!kinds.iter().any(|k| matches!(k, TokenKind::IfDef(_) | TokenKind::EndIf)));
+}
+
+#[test]
+fn ignores_directives_in_disabled_comments_and_strings() {
+    let source = "#ifdef A\n// #endif\n/* #ifdef B */ \"#endif\" '#ifdef'\n#endif 0x02";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.defines = Some(HashSet::new());
+    let tokens = lexer
+        .map(|x| x.unwrap())
+        .filter(|x| !matches!(x.kind, TokenKind::Whitespace))
+        .collect::<Vec<Token>>();
+
+    assert_eq!(tokens[1], Token::new(TokenKind::InactiveRegion, Span::new(8..52, None)));
+    assert_eq!(tokens[2].kind, TokenKind::EndIf);
+    assert_eq!(tokens[3].kind, TokenKind::Literal(str_to_bytes32("02")));
+}
@@ -0,0 +1,33 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn parses_constant_ref() {
+    let source = "[FOO]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_constant_ref().unwrap(), "FOO");
+}
+
+#[test]
+fn parses_constant_ref_with_whitespace() {
+    let source = "[ FOO ]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_constant_ref().unwrap(), "FOO");
+}
+
+#[test]
+fn fails_on_multiple_identifiers_in_constant_ref() {
+    let source = "[FOO BAR]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let err = lexer.parse_constant_ref().unwrap_err();
+    assert_eq!(
+        err,
+        LexicalError::new(LexicalErrorKind::UnexpectedToken("BAR"), Span::new(5..8, None))
+    );
+}
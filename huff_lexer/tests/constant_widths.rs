@@ -0,0 +1,29 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn accepts_32_byte_constant() {
+    let source = format!("#define constant MASK = 0x{}", "ff".repeat(32));
+    assert!(check_constant_widths(&source).is_empty());
+}
+
+#[test]
+fn warns_on_33_byte_constant() {
+    let source = format!("#define constant MASK = 0x{}", "ff".repeat(33));
+    assert_eq!(
+        check_constant_widths(&source),
+        vec![LexicalWarning::new(LexicalWarningKind::OversizedConstant, Span::new(26..92, None))]
+    );
+}
+
+#[test]
+fn warns_past_leading_zeros() {
+    let source = format!("#define constant MASK = 0x00{}", "ff".repeat(32));
+    assert_eq!(check_constant_widths(&source).len(), 1);
+}
+
+#[test]
+fn skips_free_storage_pointer_constant() {
+    let source = "#define constant SLOT = FREE_STORAGE_POINTER()";
+    assert!(check_constant_widths(source).is_empty());
+}
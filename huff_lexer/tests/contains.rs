@@ -0,0 +1,33 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn contains_macro_keyword() {
+    // The invalid character after the definition is never reached
+    let source = "#define macro MAIN() = takes(0) returns(0) {} $";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.contains(|kind| *kind == TokenKind::Macro), Ok(true));
+}
+
+#[test]
+fn does_not_contain_macro_keyword() {
+    let source = "#define constant MACRO = 0x01\n#define event Transfer(uint256)";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.contains(|kind| *kind == TokenKind::Macro), Ok(false));
+}
+
+#[test]
+fn contains_surfaces_lexical_errors() {
+    let source = "#define constant FOO = $\n#define macro MAIN() = takes(0) returns(0) {}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    assert_eq!(
+        lexer.contains(|kind| *kind == TokenKind::Macro),
+        Err(LexicalError::new(LexicalErrorKind::InvalidCharacter('$'), Span::new(23..24, None)))
+    );
+}
@@ -52,3 +52,61 @@ fn macro_context() {
         .collect::<Vec<Token>>();
     assert_eq!(tokens.get(tokens.len() - 3).unwrap().kind, TokenKind::Opcode(Opcode::Byte));
 }
+
+#[test]
+fn empty_macro_body() {
+    let source = "#define macro NOOP() = takes(0) returns(0) {}\n#define macro MAIN() = takes (0) returns (0) {stop}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let mut tokens = vec![];
+    for token in lexer.by_ref() {
+        let token = token.unwrap();
+        let is_close_brace = token.kind == TokenKind::CloseBrace;
+        if !matches!(token.kind, TokenKind::Whitespace) {
+            tokens.push(token);
+        }
+        if is_close_brace {
+            break
+        }
+    }
+
+    // The empty body is an open brace immediately followed by a close brace
+    assert_eq!(
+        tokens.iter().map(|x| x.kind.clone()).collect::<Vec<TokenKind>>(),
+        vec![
+            TokenKind::Define,
+            TokenKind::Macro,
+            TokenKind::Ident("NOOP".to_string()),
+            TokenKind::OpenParen,
+            TokenKind::CloseParen,
+            TokenKind::Assign,
+            TokenKind::Takes,
+            TokenKind::OpenParen,
+            TokenKind::Num(0),
+            TokenKind::CloseParen,
+            TokenKind::Returns,
+            TokenKind::OpenParen,
+            TokenKind::Num(0),
+            TokenKind::CloseParen,
+            TokenKind::OpenBrace,
+            TokenKind::CloseBrace,
+        ]
+    );
+    assert_eq!(tokens[14].span, Span::new(43..44, None));
+    assert_eq!(tokens[15].span, Span::new(44..45, None));
+
+    // Closing the empty body leaves the macro body context
+    assert_eq!(lexer.context, Context::Global);
+
+    // So the following definition lexes as usual
+    let tokens = lexer
+        .map(|x| x.unwrap().kind)
+        .filter(|x| !matches!(x, TokenKind::Whitespace))
+        .collect::<Vec<TokenKind>>();
+    assert_eq!(tokens[0], TokenKind::Define);
+    assert_eq!(tokens[1], TokenKind::Macro);
+    assert_eq!(tokens[6], TokenKind::Takes);
+    assert_eq!(tokens[10], TokenKind::Returns);
+    assert_eq!(tokens[15], TokenKind::Opcode(Opcode::Stop));
+}
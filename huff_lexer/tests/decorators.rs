@@ -1,5 +1,7 @@
 use huff_lexer::Lexer;
-use huff_utils::prelude::{str_to_bytes32, FullFileSource, Span, Token, TokenKind};
+use huff_utils::prelude::{
+    str_to_bytes32, FullFileSource, LexicalError, LexicalErrorKind, Span, Token, TokenKind,
+};
 use std::ops::Deref;
 
 #[test]
@@ -141,3 +143,90 @@ fn fails_to_parse_decorator_in_body() {
         }
     }
 }
+
+#[test]
+fn lexes_decorator_with_string_arg() {
+    let source = r#"#[calldata("0x00")]"#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    let tokens = lexer.map(|x| x.unwrap().kind).collect::<Vec<TokenKind>>();
+    assert_eq!(
+        tokens,
+        vec![
+            TokenKind::Pound,
+            TokenKind::OpenBracket,
+            TokenKind::Ident(String::from("calldata")),
+            TokenKind::OpenParen,
+            TokenKind::Str(String::from("0x00")),
+            TokenKind::CloseParen,
+            TokenKind::CloseBracket,
+            TokenKind::Eof,
+        ]
+    );
+}
+
+#[test]
+fn lexes_decorator_with_num_arg() {
+    let source = "#[value(1)]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    let tokens = lexer.map(|x| x.unwrap().kind).collect::<Vec<TokenKind>>();
+    assert_eq!(
+        tokens,
+        vec![
+            TokenKind::Pound,
+            TokenKind::OpenBracket,
+            TokenKind::Ident(String::from("value")),
+            TokenKind::OpenParen,
+            TokenKind::Num(1),
+            TokenKind::CloseParen,
+            TokenKind::CloseBracket,
+            TokenKind::Eof,
+        ]
+    );
+}
+
+#[test]
+fn skips_nested_args_in_decorator() {
+    let source = "#[calldata((0x01)) ]";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::Pound);
+    assert!(lexer.decorator_is_closed());
+
+    // A `]` nested in the decorator arguments does not close the decorator
+    let source = r#"#[calldata(("]")) #define"#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(
+        lexer.next().unwrap().unwrap_err(),
+        LexicalError::new(LexicalErrorKind::UnclosedDecorator, Span::new(0..1, None))
+    );
+}
+
+#[test]
+fn fails_to_lex_unclosed_decorator() {
+    // Unclosed at EOF
+    let source = r#"#[calldata("0x00")"#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(
+        lexer.next().unwrap().unwrap_err(),
+        LexicalError::new(LexicalErrorKind::UnclosedDecorator, Span::new(0..1, None))
+    );
+
+    // Unclosed before the decorated definition
+    let source = r#"
+    #[calldata("0x00")
+    #define test NUMS() = takes(0) returns(1) {}
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    let _ = lexer.next(); // whitespace
+    assert_eq!(
+        lexer.next().unwrap().unwrap_err(),
+        LexicalError::new(LexicalErrorKind::UnclosedDecorator, Span::new(5..6, None))
+    );
+}
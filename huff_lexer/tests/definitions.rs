@@ -0,0 +1,67 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+fn lex(source: &str) -> Vec<Token> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    Lexer::new(flattened_source).map(|x| x.unwrap()).collect()
+}
+
+#[test]
+fn splits_at_each_definition() {
+    let source = r#"#include "./Utils.huff"
+#define constant OWNER = FREE_STORAGE_POINTER()
+#define macro INNER() = takes(0) returns(0) {
+    0x01 { 0x02 }
+}
+#define macro MAIN() = takes(0) returns(0) {
+    INNER()
+}
+"#;
+    let tokens = lex(source);
+    let definitions = split_definitions(&tokens);
+    assert_eq!(definitions.len(), 3);
+    for definition in &definitions {
+        assert_eq!(definition[0].kind, TokenKind::Define);
+        assert!(definition.iter().all(|t| t.kind != TokenKind::Eof));
+    }
+
+    // Nested braces are kept within the body
+    let inner = definitions[1].iter().filter(|t| t.kind == TokenKind::CloseBrace).count();
+    assert_eq!(inner, 2);
+    assert_eq!(definitions[2].last().unwrap().kind, TokenKind::CloseBrace);
+
+    // The slices end where their definitions do
+    assert_eq!(definitions[0][0].span.start, source.find("#define").unwrap());
+    assert_eq!(definitions[0].last().unwrap().kind, TokenKind::FreeStoragePointer);
+    assert_eq!(definitions[2].last().unwrap().span.end, source.len() - 1);
+}
+
+#[test]
+fn ends_definitions_before_includes() {
+    let source = r#"#define constant OWNER = 0x01 // owner slot
+#include "./Utils.huff"
+#define event Transfer(address)
+#include "./Math.huff"
+#define macro MAIN() = takes(0) returns(0) {}
+"#;
+    let tokens = lex(source);
+    let definitions = split_definitions(&tokens);
+    assert_eq!(definitions.len(), 3);
+
+    // No slice reaches into the includes or the comment following the constant
+    for definition in &definitions {
+        assert!(definition.iter().all(|t| !matches!(
+            t.kind,
+            TokenKind::Include | TokenKind::Str(_) | TokenKind::Comment(_)
+        )));
+    }
+    assert_eq!(definitions[0].last().unwrap().kind, TokenKind::Literal(str_to_bytes32("01")));
+    assert_eq!(definitions[1].last().unwrap().kind, TokenKind::CloseParen);
+    assert_eq!(definitions[2].last().unwrap().kind, TokenKind::CloseBrace);
+}
+
+#[test]
+fn splits_nothing_without_definitions() {
+    assert!(split_definitions(&lex("")).is_empty());
+    assert!(split_definitions(&lex("#include \"./Utils.huff\"")).is_empty());
+}
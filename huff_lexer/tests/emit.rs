@@ -0,0 +1,97 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+use std::sync::Arc;
+
+#[test]
+fn parses_emit_statement() {
+    let source = "emit Transfer(0x00, caller, [VALUE])";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.context = Context::MacroBody;
+
+    let emit = lexer.parse_emit().unwrap().unwrap();
+    assert_eq!(emit.name, "Transfer");
+    assert_eq!(&source[emit.args.range().unwrap()], "0x00, caller, [VALUE]");
+
+    // The whole statement was consumed
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::Eof);
+}
+
+#[test]
+fn emit_as_plain_identifier() {
+    let source = "emit 0x01 add";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.context = Context::MacroBody;
+
+    assert_eq!(lexer.parse_emit(), Ok(None));
+
+    // The lexer was left untouched
+    assert_eq!(
+        lexer.next().unwrap().unwrap(),
+        Token::new(TokenKind::Ident("emit".to_string()), Span::new(0..4, None))
+    );
+}
+
+#[test]
+fn other_token_is_not_an_emit() {
+    let source = "0x01 emit";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.context = Context::MacroBody;
+
+    assert_eq!(lexer.parse_emit(), Ok(None));
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::Literal(str_to_bytes32("01")));
+
+    // Nor is the end of the source
+    let source = "";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(lexer.parse_emit(), Ok(None));
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::Eof);
+}
+
+#[test]
+fn unterminated_emit_restores_lexer() {
+    let source = "emit Transfer(0x00, caller";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.context = Context::MacroBody;
+
+    assert_eq!(lexer.parse_emit().unwrap_err().kind, LexicalErrorKind::UnexpectedEof);
+    assert_eq!(
+        lexer.next().unwrap().unwrap(),
+        Token::new(TokenKind::Ident("emit".to_string()), Span::new(0..4, None))
+    );
+}
+
+#[test]
+fn emit_args_span_is_file_relative() {
+    let a = "#define constant A = 0x01\n";
+    let b = "emit Transfer(0x00, caller)";
+    let source = format!("{a}{b}");
+    let file = |path: &str, source: &str| {
+        Arc::new(FileSource {
+            path: path.to_string(),
+            source: Some(source.to_string()),
+            ..Default::default()
+        })
+    };
+    let flattened_source = FullFileSource {
+        source: &source,
+        file: None,
+        spans: vec![
+            (file("a.huff", a), Span::new(0..a.len(), None)),
+            // File spans are matched exclusively, so leave room past the closing paren
+            (file("b.huff", b), Span::new(a.len()..source.len() + 1, None)),
+        ],
+    };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.context = Context::MacroBody;
+    // Skip the tokens of the first file
+    lexer.nth(8);
+
+    let emit = lexer.parse_emit().unwrap().unwrap();
+    assert_eq!(emit.args.file.as_ref().unwrap().path, "b.huff");
+    assert_eq!(&b[emit.args.start..emit.args.end], "0x00, caller");
+}
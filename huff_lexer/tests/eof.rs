@@ -23,3 +23,39 @@ fn end_of_file() {
     assert!(lexer.eof);
     assert!(lexer.next().is_none());
 }
+
+#[test]
+fn iterates_by_mutable_reference() {
+    let source = "#define macro HELLO_WORLD()";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    // `&mut Lexer` is an iterator through the standard library's blanket implementation, so the
+    // lexer remains usable once the loop completes
+    let mut count = 0;
+    for tok in &mut lexer {
+        assert!(tok.is_ok());
+        count += 1;
+    }
+    assert_eq!(count, 8);
+
+    assert!(lexer.eof);
+    assert!(lexer.eof_returned);
+    assert_eq!(lexer.current_span().end, source.len());
+    assert!(lexer.next().is_none());
+}
+
+#[test]
+fn significant_len_excludes_eof() {
+    let source = "#define macro HELLO_WORLD() // greets\n";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let tokens = Lexer::new(flattened_source).map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    let manual = tokens
+        .iter()
+        .filter(|t| !matches!(t.kind, TokenKind::Whitespace | TokenKind::Comment(_)))
+        .count();
+    assert_eq!(tokens.last().unwrap().kind, TokenKind::Eof);
+    assert_eq!(significant_len(&tokens), manual - 1);
+    assert_eq!(significant_len(&tokens), 5);
+}
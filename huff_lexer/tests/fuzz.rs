@@ -0,0 +1,105 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+use proptest::prelude::*;
+
+/// Keyword fragments and separators that exercise the keyword look-back disambiguation.
+const FRAGMENTS: &[&str] = &[
+    "#define",
+    "#",
+    "define",
+    "macro",
+    "fn",
+    "test",
+    "function",
+    "event",
+    "error",
+    "constant",
+    "takes",
+    "returns",
+    "view",
+    "pure",
+    "payable",
+    "nonpayable",
+    "jumptable",
+    "jumptable__packed",
+    "table",
+    "FREE_STORAGE_POINTER",
+    "MAIN",
+    "take",
+    "return",
+    ":",
+    "(",
+    ")",
+    "=",
+    "{",
+    "}",
+    " ",
+    "\n",
+    "\t",
+    "//",
+    "/*",
+    "*/",
+    "0x",
+    "1",
+];
+
+/// Lexes the source, checking the invariants the fuzzer relies on:
+/// - lexing terminates within one token per source byte, plus `Eof`, returning `Eof` at most once,
+/// - every token span satisfies `start <= end <= source.len()`,
+/// - every span covers a slice of the source on char boundaries.
+fn check_invariants(source: &str) {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    let mut eofs = 0;
+    for token in lexer.by_ref().take(source.len() + 1) {
+        let span = match token {
+            Ok(token) => {
+                if token.kind == TokenKind::Eof {
+                    eofs += 1;
+                }
+                token.span
+            }
+            Err(e) => e.span,
+        };
+        assert!(span.start <= span.end, "inverted span {span:?} for {source:?}");
+        assert!(span.end <= source.len(), "span {span:?} past the end of {source:?}");
+        assert!(source.get(span.start..span.end).is_some(), "span {span:?} splits a char");
+    }
+    assert!(eofs <= 1, "{eofs} EOF tokens for {source:?}");
+    assert!(lexer.next().is_none(), "lexing {source:?} did not terminate");
+}
+
+proptest! {
+    #[test]
+    fn keyword_fragments_respect_invariants(
+        fragments in prop::collection::vec(prop::sample::select(FRAGMENTS), 0..16)
+    ) {
+        check_invariants(&fragments.concat());
+    }
+}
+
+#[test]
+fn keyword_in_abi_args_at_end_of_source_reaches_eof() {
+    for source in ["#defineevent(pure", "#definefunction(macro", "#define event(view"] {
+        check_invariants(source);
+        let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+        let tokens = Lexer::new(flattened_source).map(|x| x.unwrap()).collect::<Vec<Token>>();
+        assert_eq!(tokens.last().unwrap().kind, TokenKind::Eof);
+    }
+}
+
+#[test]
+fn truncated_keywords_at_end_of_source() {
+    for source in ["#define mac", "#define macr", "takes", "#define macro M() = take", "return"] {
+        check_invariants(source);
+    }
+}
+
+#[test]
+fn comment_between_define_and_keyword() {
+    let source = "#define /* doc */ macro MAIN()";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let kinds = Lexer::new(flattened_source).map(|x| x.unwrap().kind).collect::<Vec<TokenKind>>();
+    assert_eq!(kinds[4], TokenKind::Macro);
+    assert_eq!(kinds[6], TokenKind::Ident("MAIN".to_string()));
+}
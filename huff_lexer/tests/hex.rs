@@ -76,3 +76,273 @@ fn converts_literal_to_hex_string() {
         assert_eq!(format!("0x{}", source), bytes32_to_string(&str_to_bytes32(source), true));
     }
 }
+
+#[test]
+fn detects_selectors() {
+    assert!(HexLiteral::new("0x12345678").unwrap().is_selector());
+    assert!(HexLiteral::new("a9059cbb").unwrap().is_selector());
+    assert!(!HexLiteral::new("0x1234").unwrap().is_selector());
+    assert!(!HexLiteral::new("0x0012345678").unwrap().is_selector());
+    assert_eq!(HexLiteral::new("0xfoo"), None);
+
+    // From a lexed token
+    let source = "0x12345678 0x1234";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let tokens = lexer
+        .map(|x| x.unwrap())
+        .filter(|x| matches!(x.kind, TokenKind::Literal(_)))
+        .collect::<Vec<Token>>();
+    assert!(HexLiteral::from_token(&tokens[0], source).unwrap().is_selector());
+    assert!(!HexLiteral::from_token(&tokens[1], source).unwrap().is_selector());
+}
+
+#[test]
+fn warns_on_suspicious_selector() {
+    let source = r#"
+    #define macro MAIN() = takes(0) returns(0) {
+        0x00 calldataload 0xE0 shr
+        dup1 0x1234 eq short jumpi
+        dup1 0x12345678 eq selector jumpi
+        0x1234 equal
+    }
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+
+    // Warnings are opt-in
+    let mut lexer = Lexer::new(flattened_source.clone());
+    lexer.by_ref().for_each(|x| assert!(x.is_ok()));
+    assert!(lexer.warnings.is_empty());
+
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    lexer.by_ref().for_each(|x| assert!(x.is_ok()));
+
+    let start = source.find("1234 eq").unwrap();
+    assert_eq!(
+        lexer.warnings,
+        vec![LexicalWarning::new(
+            LexicalWarningKind::SuspiciousSelector,
+            Span::new(start..start + 4, None)
+        )]
+    );
+}
+
+#[test]
+fn retains_written_width_of_zero_literals() {
+    let source = "0x00000000 0x0 0x00";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let tokens = lexer
+        .map(|x| x.unwrap())
+        .filter(|x| matches!(x.kind, TokenKind::Literal(_)))
+        .collect::<Vec<Token>>();
+    assert_eq!(tokens.len(), 3);
+
+    let literals = tokens
+        .iter()
+        .map(|t| HexLiteral::from_token(t, source).unwrap())
+        .collect::<Vec<HexLiteral>>();
+
+    // All are zero valued
+    for (token, literal) in tokens.iter().zip(literals.iter()) {
+        assert_eq!(token.kind, TokenKind::Literal([0u8; 32]));
+        assert_eq!(literal.value(), [0u8; 32]);
+    }
+
+    // But keep their written widths
+    assert_eq!(literals.iter().map(|l| l.byte_width()).collect::<Vec<usize>>(), vec![4, 1, 1]);
+
+    // The zero selector is still a selector
+    assert!(literals[0].is_selector());
+    assert!(!literals[1].is_selector());
+}
+
+#[test]
+fn computes_byte_width_with_leading_zeros() {
+    assert_eq!(HexLiteral::new("0x0001").unwrap().byte_width(), 2);
+    assert_eq!(HexLiteral::new("0x001").unwrap().byte_width(), 2);
+    assert_eq!(HexLiteral::new("0x1").unwrap().byte_width(), 1);
+    assert_eq!(HexLiteral::new(&format!("0x{}", "00".repeat(32))).unwrap().byte_width(), 32);
+}
+
+#[test]
+fn warns_on_non_breaking_space_in_literal() {
+    let source = "0x10\u{a0}20";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+
+    // Non-ASCII whitespace is an invalid character by default
+    let mut lexer = Lexer::new(flattened_source.clone());
+    let _ = lexer.next(); // 0x10
+    assert_eq!(
+        lexer.next(),
+        Some(Err(LexicalError::new(
+            LexicalErrorKind::InvalidCharacter('\u{a0}'),
+            Span::new(4..6, None)
+        )))
+    );
+
+    // Strict mode keeps the error, explaining it with a hint and a warning
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    let _ = lexer.next(); // 0x10
+    let err = lexer.next().unwrap().unwrap_err();
+    assert_eq!(
+        (err.kind, err.span),
+        (LexicalErrorKind::InvalidCharacter('\u{a0}'), Span::new(4..6, None))
+    );
+    assert!(err.hint.is_some());
+    assert_eq!(
+        lexer.warnings,
+        vec![LexicalWarning::new(
+            LexicalWarningKind::SuspiciousWhitespaceInLiteral,
+            Span::new(4..6, None)
+        )]
+    );
+}
+
+#[test]
+fn warns_on_non_breaking_space_before_hex_letters() {
+    let source = "0x10\u{a0}ab";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+
+    // The space is both after a literal and before hex digits
+    let _ = lexer.next(); // 0x10
+    let _ = lexer.next(); // \u{a0}
+    assert_eq!(
+        lexer.warnings,
+        vec![LexicalWarning::new(
+            LexicalWarningKind::SuspiciousWhitespaceInLiteral,
+            Span::new(4..6, None)
+        )]
+    );
+
+    // Ahead of the space, hex letters are digits too, but other letters are not
+    let source = "push \u{a0}ab";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    let _ = lexer.nth(1); // push, whitespace
+    let _ = lexer.next(); // \u{a0}
+    assert_eq!(lexer.warnings.len(), 1);
+
+    let source = "push \u{a0}xy";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    let _ = lexer.nth(1); // push, whitespace
+    let err = lexer.next().unwrap().unwrap_err();
+    assert_eq!(err.kind, LexicalErrorKind::InvalidCharacter('\u{a0}'));
+    assert!(lexer.warnings.is_empty());
+}
+
+#[test]
+fn lexes_uppercase_radix_prefixes() {
+    let source = "0XaB 0xcD";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+
+    let mut lexer = Lexer::new(flattened_source.clone());
+    let tokens = lexer.by_ref().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::new(TokenKind::Literal(str_to_bytes32("ab")), Span::new(2..4, None)),
+            Token::new(TokenKind::Whitespace, Span::new(4..5, None)),
+            Token::new(TokenKind::Literal(str_to_bytes32("cd")), Span::new(7..9, None)),
+            Token::new(TokenKind::Eof, Span::new(9..9, None)),
+        ]
+    );
+    assert!(lexer.warnings.is_empty());
+
+    // The digits keep their case in the source
+    assert_eq!(HexLiteral::from_token(&tokens[0], source).unwrap().digits, "aB");
+    assert_eq!(HexLiteral::from_token(&tokens[2], source).unwrap().digits, "cD");
+
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    lexer.by_ref().for_each(|x| assert!(x.is_ok()));
+    assert_eq!(
+        lexer.warnings,
+        vec![LexicalWarning::new(LexicalWarningKind::UppercaseRadixPrefix, Span::new(0..2, None))]
+    );
+}
+
+#[test]
+fn warns_on_ambiguous_literal() {
+    let source = "0x10xyz";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+
+    // The literal and the identifier are split
+    let mut lexer = Lexer::new(flattened_source.clone());
+    let tokens = lexer.by_ref().map(|x| x.unwrap()).collect::<Vec<Token>>();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::new(TokenKind::Literal(str_to_bytes32("10")), Span::new(2..4, None)),
+            Token::new(TokenKind::Ident("xyz".to_string()), Span::new(4..7, None)),
+            Token::new(TokenKind::Eof, Span::new(7..7, None)),
+        ]
+    );
+    assert!(lexer.warnings.is_empty());
+
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    lexer.by_ref().for_each(|x| assert!(x.is_ok()));
+    assert_eq!(
+        lexer.warnings,
+        vec![LexicalWarning::new(LexicalWarningKind::AmbiguousLiteral, Span::new(4..5, None))]
+    );
+
+    // Hex digits are part of the literal, not an identifier
+    let source = "0x10abc";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    assert_eq!(
+        lexer.next().unwrap().unwrap(),
+        Token::new(TokenKind::Literal(str_to_bytes32("10abc")), Span::new(2..7, None))
+    );
+    assert!(lexer.warnings.is_empty());
+}
+
+#[test]
+fn separated_literal_is_not_ambiguous() {
+    let source = "0x10 abc 10";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    lexer.by_ref().for_each(|x| assert!(x.is_ok()));
+    assert!(lexer.warnings.is_empty());
+}
+
+#[test]
+fn reads_hex_literal_as_ascii() {
+    let source = "0x68656c6c6f 0xdeadbeef";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let literals = lexer
+        .map(|x| x.unwrap())
+        .filter_map(|t| HexLiteral::from_token(&t, source))
+        .map(|l| l.as_ascii())
+        .collect::<Vec<Option<String>>>();
+    assert_eq!(literals, vec![Some("hello".to_string()), None]);
+}
+
+#[test]
+fn rejects_hex_literal_wider_than_32_bytes() {
+    let source = format!("0x{}", "01".repeat(33));
+    let flattened_source = FullFileSource { source: &source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(
+        lexer.next().unwrap(),
+        Err(LexicalError::new(LexicalErrorKind::OversizedLiteral, Span::new(2..68, None)))
+    );
+
+    // Leading zeros count towards the written width
+    let source = format!("0x00{}", "01".repeat(32));
+    let flattened_source = FullFileSource { source: &source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(lexer.next().unwrap().unwrap_err().kind, LexicalErrorKind::OversizedLiteral);
+}
@@ -0,0 +1,30 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn highlights_readme_source() {
+    let source = "#define macro HELLO_WORLD()";
+    assert_eq!(
+        highlight(source),
+        vec![
+            (Span::new(0..7, None), TokenCategory::Keyword),
+            (Span::new(8..13, None), TokenCategory::Keyword),
+            (Span::new(14..25, None), TokenCategory::Identifier),
+            (Span::new(25..26, None), TokenCategory::Punctuation),
+            (Span::new(26..27, None), TokenCategory::Punctuation),
+        ]
+    );
+}
+
+#[test]
+fn highlights_errors_and_recovers() {
+    let source = "#define $ macro";
+    assert_eq!(
+        highlight(source),
+        vec![
+            (Span::new(0..7, None), TokenCategory::Keyword),
+            (Span::new(8..9, None), TokenCategory::Error),
+            (Span::new(10..15, None), TokenCategory::Keyword),
+        ]
+    );
+}
@@ -0,0 +1,36 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn lexes_custom_ident_chars() {
+    let source = "foo$bar";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+
+    // `$` is not an identifier character by default
+    let lexer = Lexer::new(flattened_source.clone());
+    let tokens = lexer.collect::<Vec<_>>();
+    assert_eq!(
+        tokens[0],
+        Ok(Token::new(TokenKind::Ident("foo".to_string()), Span::new(0..3, None)))
+    );
+    assert!(tokens[1].is_err());
+
+    let lexer = Lexer::new(flattened_source).with_ident_chars(|c| is_ident_continue(c) || c == '$');
+    let tokens = lexer.map(|x| x.unwrap()).collect::<Vec<Token>>();
+    assert_eq!(
+        tokens,
+        vec![
+            Token::new(TokenKind::Ident("foo$bar".to_string()), Span::new(0..7, None)),
+            Token::new(TokenKind::Eof, Span::new(7..7, None)),
+        ]
+    );
+}
+
+#[test]
+fn lexes_custom_ident_start() {
+    let source = "$foo";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source).with_ident_start(|c| is_ident_start(c) || c == '$');
+    let tokens = lexer.map(|x| x.unwrap()).collect::<Vec<Token>>();
+    assert_eq!(tokens[0], Token::new(TokenKind::Ident("$foo".to_string()), Span::new(0..4, None)));
+}
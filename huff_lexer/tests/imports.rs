@@ -181,3 +181,15 @@ fn include_with_string_single_quote() {
     assert_eq!(lexer.current_span().end, source.len());
     assert!(lexer.eof);
 }
+
+#[test]
+fn include_registry_specifier_verbatim() {
+    let path = "github.com/org/repo@v1/lib%20v2.huff";
+    let source = format!("#include \"{}\"", path);
+    let flattened_source = FullFileSource { source: &source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    let tokens = lexer.map(|x| x.unwrap()).collect::<Vec<Token>>();
+    assert_eq!(tokens[2], Token::new(TokenKind::Str(path.to_string()), Span::new(9..47, None)));
+    assert_eq!(Lexer::lex_imports(&source), vec![path.to_string()]);
+}
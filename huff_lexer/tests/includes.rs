@@ -0,0 +1,29 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn extracts_includes() {
+    let source = r#"
+    /* Imports */
+    #include "./utils/Ownable.huff"
+    #include '../lib/Address.huff'
+
+    #define macro MAIN() = takes(0) returns(0) {}
+    "#;
+    let first = source.find("\"./utils").unwrap();
+    let second = source.find("'../lib").unwrap();
+
+    assert_eq!(
+        extract_includes(source).unwrap(),
+        vec![
+            ("./utils/Ownable.huff".to_string(), Span::new(first..first + 22, None)),
+            ("../lib/Address.huff".to_string(), Span::new(second..second + 21, None)),
+        ]
+    );
+}
+
+#[test]
+fn extracts_no_includes() {
+    let source = "#define constant OWNER = FREE_STORAGE_POINTER()";
+    assert_eq!(extract_includes(source).unwrap(), vec![]);
+}
@@ -0,0 +1,23 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn reports_mixed_indentation() {
+    let source =
+        "#define macro MAIN() = takes(0) returns(0) {\n    0x00\n\t\tdup1\n \tpop\n\n    pop\n}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let tokens = Lexer::new(flattened_source).map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    assert_eq!(
+        indentation_report(&tokens, source),
+        vec![
+            (0, IndentKind::None),
+            (4, IndentKind::Spaces),
+            (2, IndentKind::Tabs),
+            (2, IndentKind::Mixed),
+            (0, IndentKind::None),
+            (4, IndentKind::Spaces),
+            (0, IndentKind::None),
+        ]
+    );
+}
@@ -0,0 +1,51 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn tracks_last_significant_token() {
+    let source = "#define macro /* block */ MAIN\n// line comment\n  () ";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(lexer.last_significant_token(), None);
+
+    let _ = lexer.next(); // #define
+    assert_eq!(
+        lexer.last_significant_token(),
+        Some(&Token::new(TokenKind::Define, Span::new(0..7, None)))
+    );
+
+    let _ = lexer.next(); // whitespace
+    let _ = lexer.next(); // macro
+    assert_eq!(
+        lexer.last_significant_token(),
+        Some(&Token::new(TokenKind::Macro, Span::new(8..13, None)))
+    );
+
+    // Whitespace and comments are skipped
+    let _ = lexer.next(); // whitespace
+    let _ = lexer.next(); // block comment
+    let _ = lexer.next(); // whitespace
+    assert_eq!(
+        lexer.last_significant_token(),
+        Some(&Token::new(TokenKind::Macro, Span::new(8..13, None)))
+    );
+
+    let _ = lexer.next(); // MAIN
+    let _ = lexer.next(); // whitespace
+    let _ = lexer.next(); // line comment
+    let ident = Token::new(TokenKind::Ident("MAIN".to_string()), Span::new(26..30, None));
+    assert_eq!(lexer.last_significant_token(), Some(&ident));
+    // Whereas the lookback keeps comments
+    assert!(matches!(lexer.lookback.as_ref().unwrap().kind, TokenKind::Comment(_)));
+
+    let _ = lexer.next(); // whitespace
+    let _ = lexer.next(); // (
+    let _ = lexer.next(); // )
+    let _ = lexer.next(); // whitespace
+    let _ = lexer.next(); // EOF
+    assert!(lexer.eof_returned);
+    assert_eq!(
+        lexer.last_significant_token(),
+        Some(&Token::new(TokenKind::CloseParen, Span::new(50..51, None)))
+    );
+}
@@ -0,0 +1,43 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+fn significant_kinds(lexer: Lexer) -> Vec<TokenKind> {
+    lexer
+        .map(|x| x.unwrap().kind)
+        .filter(|k| !matches!(k, TokenKind::Whitespace | TokenKind::Eof))
+        .collect()
+}
+
+#[test]
+fn lexes_modifier_in_signature_position() {
+    let source = "#define macro FOO() = unchecked takes(0) returns(0) { unchecked }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source).with_modifiers(["unchecked"]);
+
+    let kinds = significant_kinds(lexer);
+    assert_eq!(kinds[6], TokenKind::Modifier("unchecked".to_string()));
+    assert_eq!(kinds[7], TokenKind::Takes);
+    assert_eq!(kinds[kinds.len() - 2], TokenKind::Ident("unchecked".to_string()));
+}
+
+#[test]
+fn lexes_modifier_after_stack_annotations() {
+    let source = "#define macro FOO(unchecked) = takes(0) returns(0) unchecked {}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source).with_modifiers(["unchecked"]);
+
+    let kinds = significant_kinds(lexer);
+    assert_eq!(kinds[4], TokenKind::Ident("unchecked".to_string()));
+    assert_eq!(kinds[15], TokenKind::Modifier("unchecked".to_string()));
+    assert_eq!(kinds[16], TokenKind::OpenBrace);
+}
+
+#[test]
+fn modifiers_are_identifiers_when_not_configured() {
+    let source = "#define macro FOO() = unchecked {}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    let kinds = significant_kinds(lexer);
+    assert_eq!(kinds[6], TokenKind::Ident("unchecked".to_string()));
+}
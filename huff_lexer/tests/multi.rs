@@ -0,0 +1,26 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn tags_tokens_with_their_source() {
+    let sources = [(SourceId(0), "#define"), (SourceId(1), "  takes")];
+    let tokens = MultiLexer::new(&sources)
+        .map(|(id, token)| (id, token.unwrap()))
+        .collect::<Vec<(SourceId, Token)>>();
+
+    assert_eq!(
+        tokens,
+        vec![
+            (SourceId(0), Token::new(TokenKind::Define, Span::new(0..7, None))),
+            (SourceId(0), Token::new(TokenKind::Eof, Span::new(7..7, None))),
+            (SourceId(1), Token::new(TokenKind::Whitespace, Span::new(0..2, None))),
+            (SourceId(1), Token::new(TokenKind::Ident("takes".to_string()), Span::new(2..7, None))),
+            (SourceId(1), Token::new(TokenKind::Eof, Span::new(7..7, None))),
+        ]
+    );
+}
+
+#[test]
+fn lexes_no_sources() {
+    assert_eq!(MultiLexer::new(&[]).count(), 0);
+}
@@ -33,3 +33,118 @@ fn lexes_large_numbers() {
     assert_eq!(lexer.current_span().end, source.len());
     assert!(lexer.eof);
 }
+
+#[test]
+fn lexes_single_zero_at_eof() {
+    let source = "0";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Num(0), Span::new(0..1, None)));
+    assert!(lexer.eof);
+}
+
+#[test]
+fn lexes_leading_zero_as_decimal_by_default() {
+    let source = "0123";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(lexer.leading_zero, LeadingZeroPolicy::Decimal);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Num(123), Span::new(0..4, None)));
+}
+
+#[test]
+fn lexes_leading_zero_as_octal() {
+    let source = "0123";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.leading_zero = LeadingZeroPolicy::Octal;
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Num(0o123), Span::new(0..4, None)));
+
+    // Digits outside of the octal range are invalid
+    let source = "0189";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.leading_zero = LeadingZeroPolicy::Octal;
+
+    let err = lexer.next().unwrap().unwrap_err();
+    assert_eq!(
+        err,
+        LexicalError::new(LexicalErrorKind::InvalidCharacter('8'), Span::new(0..4, None))
+    );
+}
+
+#[test]
+fn rejects_leading_zero() {
+    let source = "0123";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.leading_zero = LeadingZeroPolicy::Reject;
+
+    let err = lexer.next().unwrap().unwrap_err();
+    assert_eq!(err, LexicalError::new(LexicalErrorKind::LeadingZero, Span::new(0..4, None)));
+
+    // A lone zero is not affected by the policy
+    let source = "0";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.leading_zero = LeadingZeroPolicy::Reject;
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Num(0), Span::new(0..1, None)));
+}
+
+#[test]
+fn warns_on_ambiguous_decimal_literal() {
+    let source = "10abc";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    lexer.by_ref().for_each(|x| assert!(x.is_ok()));
+    assert_eq!(
+        lexer.warnings,
+        vec![LexicalWarning::new(LexicalWarningKind::AmbiguousLiteral, Span::new(2..3, None))]
+    );
+}
+
+#[test]
+fn fails_on_decimal_overflow() {
+    let source = "18446744073709551616";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(
+        lexer.next().unwrap(),
+        Err(LexicalError::new(LexicalErrorKind::NumOverflow, Span::new(0..20, None)))
+    );
+
+    // The largest usize still lexes
+    let source = "18446744073709551615";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::Num(usize::MAX));
+}
+
+#[test]
+fn fails_on_octal_overflow() {
+    let source = "02000000000000000000000";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.leading_zero = LeadingZeroPolicy::Octal;
+
+    assert_eq!(
+        lexer.next().unwrap(),
+        Err(LexicalError::new(LexicalErrorKind::NumOverflow, Span::new(0..23, None)))
+    );
+
+    let source = "01777777777777777777777";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.leading_zero = LeadingZeroPolicy::Octal;
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::Num(usize::MAX));
+}
@@ -0,0 +1,36 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+fn body_tokens(source: &str) -> Vec<Token> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let tokens = Lexer::new(flattened_source).map(|x| x.unwrap()).collect::<Vec<Token>>();
+    section_tokens(&tokens, "MAIN").unwrap().to_vec()
+}
+
+#[test]
+fn flags_delegatecall() {
+    let source = r#"#define macro MAIN() = takes(0) returns(0) {
+    0x00 0x00 calldatasize 0x00 0x00 sload gas delegatecall
+    0x00 sstore
+}"#;
+    let summary = opcode_summary(&body_tokens(source));
+
+    assert!(summary.delegatecall);
+    assert!(!summary.selfdestruct && !summary.callcode && !summary.create2);
+    assert_eq!(summary.total, 5);
+    assert_eq!(summary.categories.get(&OpcodeCategory::Storage), Some(&2));
+    assert_eq!(summary.categories.get(&OpcodeCategory::System), Some(&1));
+}
+
+#[test]
+fn does_not_flag_safe_macro() {
+    let source = r#"#define macro MAIN() = takes(0) returns(0) {
+    0x01 0x02 add 0x00 mstore 0x20 0x00 return
+}"#;
+    let summary = opcode_summary(&body_tokens(source));
+
+    assert!(!summary.delegatecall);
+    assert!(!summary.selfdestruct && !summary.callcode && !summary.create2);
+    assert_eq!(summary.total, 3);
+    assert_eq!(opcode_summary(&[]), OpcodeSummary::default());
+}
@@ -32,3 +32,13 @@ fn opcodes() {
         );
     }
 }
+
+#[test]
+fn push0_is_not_an_opcode() {
+    let source = "#define macro MAIN() = takes(0) returns(0) { push0 }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let kinds = lexer.map(|x| x.unwrap().kind).collect::<Vec<TokenKind>>();
+    assert!(kinds.contains(&TokenKind::Ident("push0".to_string())));
+    assert!(!OPCODES.contains(&"push0"));
+}
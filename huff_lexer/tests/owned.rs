@@ -0,0 +1,37 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn owned_tokens_outlive_source() {
+    let source = String::from("#define macro MAIN() = takes(0) returns(0) {}");
+    let tokens = {
+        let flattened_source = FullFileSource { source: &source, file: None, spans: vec![] };
+        Lexer::new(flattened_source).into_owned_tokens().unwrap()
+    };
+    drop(source);
+
+    let idents = tokens
+        .iter()
+        .filter_map(|t| match &t.kind {
+            OwnedTokenKind::Ident(name) => Some(name.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<&str>>();
+    assert_eq!(idents, vec!["MAIN"]);
+    assert_eq!(tokens.last().unwrap().kind, TokenKind::Eof);
+}
+
+#[test]
+fn owned_tokens_report_lexical_errors() {
+    let source = "\"unterminated";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let err = Lexer::new(flattened_source).into_owned_tokens().unwrap_err();
+    assert_eq!(err.kind, LexicalErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn converts_token_to_owned() {
+    let token = Token::new(TokenKind::Ident("foo".to_string()), Span::new(0..3, None));
+    let owned: OwnedToken = token.to_owned_token();
+    assert_eq!(owned, token);
+}
@@ -0,0 +1,46 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn resets_to_token_boundary() {
+    let source = "#define constant A = 0x01\n#define constant B = 0x02";
+    let offset = source.find("#define constant B").unwrap();
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+
+    // Lex the whole source, then restart at the second definition
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.by_ref().for_each(|x| assert!(x.is_ok()));
+    assert!(lexer.eof);
+    lexer.reset_to(offset);
+    let tokens = lexer.map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    let fresh_source = FullFileSource { source: &source[offset..], file: None, spans: vec![] };
+    let expected = Lexer::new(fresh_source)
+        .map(|x| {
+            let token = x.unwrap();
+            Token::new(
+                token.kind,
+                Span::new(token.span.start + offset..token.span.end + offset, None),
+            )
+        })
+        .collect::<Vec<Token>>();
+
+    assert_eq!(tokens, expected);
+    assert_eq!(tokens[2].kind, TokenKind::Constant);
+}
+
+#[test]
+fn clamps_reset_offset() {
+    let source = "é 1";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    // Offset 1 is within the two byte 'é', so lexing restarts before it
+    lexer.reset_to(1);
+    assert_eq!(*lexer.current_span(), Span::new(0..0, None));
+
+    // Offsets past the end restart at the end of the source
+    lexer.reset_to(10);
+    assert_eq!(lexer.next(), Some(Ok(Token::new(TokenKind::Eof, Span::new(4..4, None)))));
+    assert_eq!(lexer.next(), None);
+}
@@ -0,0 +1,65 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+const SOURCE: &str = r#"
+#define macro CONSTRUCTOR() = takes(0) returns(0) {
+    0x00 sload
+}
+
+#define macro MAIN() = takes(0) returns(0) {
+    // Dispatch
+    0x00 calldataload 0xE0 shr
+    dup1 0x01 eq success jumpi
+    success: {
+        stop
+    }
+}
+"#;
+
+#[test]
+fn extracts_named_macro_body() {
+    let flattened_source = FullFileSource { source: SOURCE, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    let body = section_tokens(&tokens, "CONSTRUCTOR").unwrap();
+    let kinds = body
+        .iter()
+        .map(|t| t.kind.clone())
+        .filter(|k| !matches!(k, TokenKind::Whitespace))
+        .collect::<Vec<TokenKind>>();
+    assert_eq!(
+        kinds,
+        vec![TokenKind::Literal(str_to_bytes32("00")), TokenKind::Opcode(Opcode::Sload)]
+    );
+
+    // Nested braces are kept within the body
+    let body = section_tokens(&tokens, "MAIN").unwrap();
+    assert!(matches!(body.first().unwrap().kind, TokenKind::Whitespace));
+    assert_eq!(body.iter().filter(|t| t.kind == TokenKind::OpenBrace).count(), 1);
+    assert_eq!(body.iter().filter(|t| t.kind == TokenKind::CloseBrace).count(), 1);
+    assert_eq!(
+        body.iter().rev().find(|t| !matches!(t.kind, TokenKind::Whitespace)).unwrap().kind,
+        TokenKind::CloseBrace
+    );
+    assert!(body.iter().any(|t| t.kind == TokenKind::Opcode(Opcode::Stop)));
+}
+
+#[test]
+fn returns_none_for_absent_macro() {
+    let flattened_source = FullFileSource { source: SOURCE, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    assert_eq!(section_tokens(&tokens, "TRANSFER"), None);
+}
+
+#[test]
+fn returns_none_for_unterminated_body() {
+    let source = "#define macro MAIN() = takes(0) returns(0) { stop";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let tokens = lexer.into_iter().map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    assert_eq!(section_tokens(&tokens, "MAIN"), None);
+}
@@ -0,0 +1,93 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn spans_are_contiguous() {
+    let source = "#define macro MAIN() = takes(0) returns(0) {\n  0x01 dup1 // comment\n}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    let mut previous_end = 0;
+    for token in lexer {
+        let token = token.unwrap();
+        assert!(token.span.start <= token.span.end);
+        assert!(token.span.end <= source.len());
+        // Hex literal spans skip the `0x` prefix
+        let prefix = if matches!(token.kind, TokenKind::Literal(_)) { 2 } else { 0 };
+        assert_eq!(token.span.start, previous_end + prefix);
+        previous_end = token.span.end;
+    }
+    assert_eq!(previous_end, source.len());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "does not follow previous end")]
+fn corrupted_span_triggers_assertion() {
+    let source = "#define macro MAIN()";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let _ = lexer.next(); // #define
+
+    // Rewind the span without rewinding the source
+    lexer.current_span_mut().end = 3;
+    let _ = lexer.next();
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "past source length")]
+fn out_of_bounds_span_triggers_assertion() {
+    let source = "#define macro MAIN()";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let _ = lexer.next(); // #define
+
+    lexer.current_span_mut().end = source.len();
+    let _ = lexer.next();
+}
+
+/// Lexes the source, checking that every token span is a byte range slicing the source.
+fn lex_byte_spans(source: &str) -> Vec<Token> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let tokens = Lexer::new(flattened_source).map(|x| x.unwrap()).collect::<Vec<Token>>();
+    for token in &tokens {
+        assert!(source.get(token.span.start..token.span.end).is_some(), "{token:?}");
+    }
+    assert_eq!(tokens.last().unwrap().span, Span::new(source.len()..source.len(), None));
+    tokens
+}
+
+#[test]
+fn spans_after_multibyte_comment() {
+    let source = "// héllo ✓\nMAIN";
+    let tokens = lex_byte_spans(source);
+    assert_eq!(tokens[0].span, Span::new(0..13, None));
+    assert_eq!(
+        tokens[2],
+        Token::new(TokenKind::Ident("MAIN".to_string()), Span::new(14..18, None))
+    );
+
+    let source = "/* ✓✓ */ 0x01";
+    let tokens = lex_byte_spans(source);
+    assert_eq!(tokens[0].span, Span::new(0..12, None));
+    assert_eq!(&source[tokens[2].span.start..tokens[2].span.end], "01");
+}
+
+#[test]
+fn spans_after_multibyte_string() {
+    let source = "\"日本\" MAIN";
+    let tokens = lex_byte_spans(source);
+    assert_eq!(tokens[0], Token::new(TokenKind::Str("日本".to_string()), Span::new(0..8, None)));
+    assert_eq!(tokens[2], Token::new(TokenKind::Ident("MAIN".to_string()), Span::new(9..13, None)));
+}
+
+#[test]
+fn spans_after_multibyte_identifier() {
+    let source = "café MAIN";
+    let tokens = lex_byte_spans(source);
+    assert_eq!(tokens[0], Token::new(TokenKind::Ident("café".to_string()), Span::new(0..5, None)));
+    assert_eq!(tokens[2], Token::new(TokenKind::Ident("MAIN".to_string()), Span::new(6..10, None)));
+}
@@ -0,0 +1,137 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn parses_stack_annotation() {
+    let source = "takes (2) returns (1) {";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_stack_annotation(), Ok((Some(2), Some(1))));
+
+    // The token following the annotations is left to the caller
+    let _ = lexer.next(); // whitespace
+    assert_eq!(lexer.next().unwrap().unwrap().kind, TokenKind::OpenBrace);
+}
+
+#[test]
+fn parses_stack_annotation_in_signature() {
+    let source = "#define macro MAIN() = takes(0) returns(3) {}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    // Lex up to the assignment so `takes` is lexed as a keyword
+    while lexer.next().unwrap().unwrap().kind != TokenKind::Assign {}
+
+    assert_eq!(lexer.parse_stack_annotation(), Ok((Some(0), Some(3))));
+}
+
+#[test]
+fn parses_reversed_stack_annotation() {
+    let source = "returns(1) takes(2)";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_stack_annotation(), Ok((Some(2), Some(1))));
+}
+
+#[test]
+fn parses_takes_without_returns() {
+    let source = "takes (2) {";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_stack_annotation(), Ok((Some(2), None)));
+
+    // EOF directly after the annotation is fine as well
+    let source = "takes (2)";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_stack_annotation(), Ok((Some(2), None)));
+}
+
+#[test]
+fn fails_on_non_numeric_takes() {
+    let source = "takes (x) returns (1)";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(
+        lexer.parse_stack_annotation(),
+        Err(LexicalError::new(LexicalErrorKind::UnexpectedToken("x"), Span::new(7..8, None)))
+    );
+}
+
+#[test]
+fn fails_on_unclosed_parens() {
+    let source = "takes (2 returns (1)";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(
+        lexer.parse_stack_annotation(),
+        Err(LexicalError::new(
+            LexicalErrorKind::UnexpectedToken("returns"),
+            Span::new(9..16, None)
+        ))
+    );
+}
+
+#[test]
+fn fails_on_missing_annotation() {
+    let source = "{ stop }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(
+        lexer.parse_stack_annotation(),
+        Err(LexicalError::new(LexicalErrorKind::UnexpectedToken("{"), Span::new(0..1, None)))
+    );
+}
+
+#[test]
+fn parses_paren_less_stack_annotation() {
+    let source = "takes 2 returns (1) {";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_stack_annotation(), Ok((Some(2), Some(1))));
+
+    let source = "#define macro MAIN() = takes(0) returns 3 {}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    while lexer.next().unwrap().unwrap().kind != TokenKind::Assign {}
+
+    assert_eq!(lexer.parse_stack_annotation(), Ok((Some(0), Some(3))));
+}
+
+#[test]
+fn lexes_paren_less_returns_keyword() {
+    let source = "#define macro MAIN() = takes(0) returns 3 {}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    let kinds = lexer.map(|x| x.unwrap().kind).collect::<Vec<TokenKind>>();
+    assert!(kinds.contains(&TokenKind::Returns));
+    assert!(kinds.contains(&TokenKind::Num(3)));
+}
+
+#[test]
+fn fails_on_returns_at_eof() {
+    let source = "takes (0) returns";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    assert_eq!(lexer.parse_stack_annotation().unwrap_err().kind, LexicalErrorKind::UnexpectedEof);
+
+    // Lexing a signature ending with `returns` reaches EOF without panicking
+    let source = "#define macro MAIN() = takes(0) returns";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+
+    let tokens = lexer.map(|x| x.unwrap()).collect::<Vec<Token>>();
+    assert_eq!(tokens[tokens.len() - 2].kind, TokenKind::Ident("returns".to_string()));
+    assert_eq!(tokens.last().unwrap().kind, TokenKind::Eof);
+}
@@ -0,0 +1,30 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn sums_net_stack_effect_of_macro_body() {
+    let source = r#"
+    #define macro ADD_TWO() = takes(1) returns(1) {
+        // Add two to the input
+        0x02 add
+    }
+
+    #define macro STORE() = takes(2) returns(0) {
+        dup2 dup2 sstore pop pop
+    }
+    "#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let tokens = Lexer::new(flattened_source).map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    assert_eq!(net_stack_effect(section_tokens(&tokens, "ADD_TWO").unwrap()), Some(0));
+    assert_eq!(net_stack_effect(section_tokens(&tokens, "STORE").unwrap()), Some(-2));
+}
+
+#[test]
+fn unknown_stack_effect_of_macro_call() {
+    let source = "#define macro MAIN() = takes(0) returns(0) { 0x01 ADD_TWO() }";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let tokens = Lexer::new(flattened_source).map(|x| x.unwrap()).collect::<Vec<Token>>();
+
+    assert_eq!(net_stack_effect(section_tokens(&tokens, "MAIN").unwrap()), None);
+}
@@ -0,0 +1,63 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+#[test]
+fn lexes_empty_double_quoted_string() {
+    let source = r#""""#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Str(String::new()), Span::new(0..2, None)));
+    assert!(tok.kind.is_empty_str());
+
+    // We covered the whole source
+    assert_eq!(lexer.current_span().end, source.len());
+    assert!(lexer.eof);
+}
+
+#[test]
+fn lexes_empty_single_quoted_string() {
+    let source = "''";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Str(String::new()), Span::new(0..2, None)));
+    assert!(tok.kind.is_empty_str());
+
+    // We covered the whole source
+    assert_eq!(lexer.current_span().end, source.len());
+    assert!(lexer.eof);
+}
+
+#[test]
+fn lexes_single_char_string() {
+    let source = r#""a""#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+
+    let tok = lexer.next().unwrap().unwrap();
+    assert_eq!(tok, Token::new(TokenKind::Str("a".to_string()), Span::new(0..3, None)));
+    assert!(!tok.kind.is_empty_str());
+
+    // Only string literals can be empty strings
+    assert!(!TokenKind::Ident(String::new()).is_empty_str());
+}
+
+#[test]
+fn hints_at_mismatched_quote() {
+    let source = r#""value'"#;
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+
+    // Hints are opt-in
+    let mut lexer = Lexer::new(flattened_source.clone());
+    let err = lexer.next().unwrap().unwrap_err();
+    assert_eq!(err, LexicalError::new(LexicalErrorKind::UnexpectedEof, Span::new(0..7, None)));
+
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.strict = true;
+    let err = lexer.next().unwrap().unwrap_err();
+    assert_eq!(err.kind, LexicalErrorKind::UnexpectedEof);
+    assert!(err.hint.unwrap().contains("mismatched '"));
+}
@@ -0,0 +1,68 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+use std::sync::Arc;
+
+#[test]
+fn pairs_token_with_preceding_comment() {
+    let source = "#define macro /* block */ // line\n  MAIN";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let tokens = lexer.tokens_with_leading_trivia_span().map(|x| x.unwrap()).collect::<Vec<_>>();
+
+    assert_eq!(tokens.len(), 4);
+    assert_eq!(
+        tokens[2].0,
+        Token::new(TokenKind::Ident("MAIN".to_string()), Span::new(36..40, None))
+    );
+    assert_eq!(tokens[2].1, Span::new(13..36, None));
+    assert_eq!(&source[tokens[2].1.range().unwrap()], " /* block */ // line\n  ");
+}
+
+#[test]
+fn empty_trivia_span_without_preceding_trivia() {
+    let source = "#define macro MAIN()";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source);
+    let tokens = lexer.tokens_with_leading_trivia_span().map(|x| x.unwrap()).collect::<Vec<_>>();
+
+    // The first token starts the source
+    assert_eq!(tokens[0].0, Token::new(TokenKind::Define, Span::new(0..7, None)));
+    assert_eq!(tokens[0].1, Span::new(0..0, None));
+
+    // The open paren directly follows the macro name
+    assert_eq!(tokens[3].0, Token::new(TokenKind::OpenParen, Span::new(18..19, None)));
+    assert_eq!(tokens[3].1, Span::new(18..18, None));
+}
+
+#[test]
+fn trivia_span_is_relative_to_dependency_file() {
+    let a = "#define constant A = 0x01\n";
+    let b = "// doc\n#define constant B = 0x02\n";
+    let source = format!("{a}{b}");
+    let file = |path: &str, source: &str| {
+        Arc::new(FileSource {
+            path: path.to_string(),
+            source: Some(source.to_string()),
+            ..Default::default()
+        })
+    };
+    let flattened_source = FullFileSource {
+        source: &source,
+        file: None,
+        spans: vec![
+            (file("a.huff", a), Span::new(0..a.len(), None)),
+            (file("b.huff", b), Span::new(a.len()..source.len(), None)),
+        ],
+    };
+    let lexer = Lexer::new(flattened_source);
+    let tokens = lexer.tokens_with_leading_trivia_span().map(|x| x.unwrap()).collect::<Vec<_>>();
+
+    let (define, trivia) = &tokens[5];
+    assert_eq!(define.kind, TokenKind::Define);
+    assert_eq!(define.span.file.as_ref().unwrap().path, "b.huff");
+    assert_eq!((define.span.start, define.span.end), (7, 14));
+
+    // The trivia is in the same file as its token, starting at the beginning of the file
+    assert_eq!(trivia.file.as_ref().unwrap().path, "b.huff");
+    assert_eq!(&b[trivia.start..trivia.end], "// doc\n");
+}
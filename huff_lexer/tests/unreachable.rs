@@ -0,0 +1,34 @@
+use huff_lexer::*;
+use huff_utils::prelude::*;
+
+fn lex(source: &str) -> Vec<Token> {
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    Lexer::new(flattened_source).map(|x| x.unwrap()).collect()
+}
+
+#[test]
+fn flags_code_after_return() {
+    let source =
+        "#define macro MAIN() = takes(0) returns(0) {\n    0x00 0x00 return\n    0x01 pop\n}";
+    let start = source.find("01 pop").unwrap();
+
+    assert_eq!(
+        find_unreachable_code(&lex(source)),
+        vec![LexicalWarning::new(
+            LexicalWarningKind::UnreachableCode,
+            Span::new(start..start + 6, None)
+        )]
+    );
+}
+
+#[test]
+fn label_makes_code_reachable() {
+    let source = "#define macro MAIN() = takes(0) returns(0) {\n    0x00 0x00 return\n    cont:\n        0x01 pop\n}";
+    assert!(find_unreachable_code(&lex(source)).is_empty());
+}
+
+#[test]
+fn reachability_is_reset_between_macros() {
+    let source = "#define macro A() = takes(0) returns(0) {\n    stop\n}\n#define macro B() = takes(0) returns(0) {\n    0x01 pop\n}";
+    assert!(find_unreachable_code(&lex(source)).is_empty());
+}
@@ -49,9 +49,19 @@ impl Parser {
 
     /// Parse
     pub fn parse(&mut self) -> Result<Contract, ParserError> {
-        // Remove all whitespaces, newlines, and comments first
-        self.tokens
-            .retain(|token| !matches!(token.kind, TokenKind::Whitespace | TokenKind::Comment(_)));
+        // Remove all whitespaces, newlines, comments, conditional compilation tokens, and modifiers
+        // first
+        self.tokens.retain(|token| {
+            !matches!(
+                token.kind,
+                TokenKind::Whitespace |
+                    TokenKind::Comment(_) |
+                    TokenKind::IfDef(_) |
+                    TokenKind::EndIf |
+                    TokenKind::InactiveRegion |
+                    TokenKind::Modifier(_)
+            )
+        });
 
         // Reset the initial token
         self.reset();
@@ -60,3 +60,20 @@ fn test_parses_literal_constant() {
         }
     );
 }
+
+#[test]
+fn test_skips_inactive_constant() {
+    let source = "#ifdef DEBUG\n#define constant FOO = 0x01\n#endif\n#define constant BAR = 0x02";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let mut lexer = Lexer::new(flattened_source);
+    lexer.defines = Some(std::collections::HashSet::new());
+    let tokens = lexer.map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+    let contract = parser.parse().unwrap();
+    assert_eq!(parser.current_token.kind, TokenKind::Eof);
+
+    let constants = contract.constants.lock().unwrap();
+    assert_eq!(constants.len(), 1);
+    assert_eq!(constants[0].name, "BAR");
+    assert_eq!(constants[0].value, ConstVal::Literal(str_to_bytes32("02")));
+}
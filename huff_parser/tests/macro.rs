@@ -1229,3 +1229,17 @@ fn empty_test_with_multi_flag_decorator() {
     assert_eq!(macro_definition, expected);
     assert_eq!(parser.current_token.kind, TokenKind::Eof);
 }
+
+#[test]
+fn macro_with_modifier() {
+    let source = "#define macro FOO() = unchecked takes(1) returns(2) {}";
+    let flattened_source = FullFileSource { source, file: None, spans: vec![] };
+    let lexer = Lexer::new(flattened_source).with_modifiers(["unchecked"]);
+    let tokens = lexer.map(|x| x.unwrap()).collect::<Vec<Token>>();
+    let mut parser = Parser::new(tokens, None);
+
+    // Modifiers are skipped by the parser
+    let macro_definition = parser.parse().unwrap().macros[0].clone();
+    assert_eq!(parser.current_token.kind, TokenKind::Eof);
+    assert_eq!((macro_definition.takes, macro_definition.returns), (1, 2));
+}
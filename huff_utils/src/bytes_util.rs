@@ -1,5 +1,7 @@
 use std::num::ParseIntError;
 
+use crate::token::HexLiteral;
+
 use tiny_keccak::{Hasher, Keccak};
 
 /// Convert a string slice to a `[u8; 32]`
@@ -65,6 +67,12 @@ pub fn str_to_vec(s: &str) -> Result<Vec<u8>, std::num::ParseIntError> {
     bytes
 }
 
+/// Interpret a hex literal, with or without the `0x` prefix, as a printable ASCII string.
+/// i.e. 0x68656c6c6f becomes `"hello"`, whereas 0xdeadbeef is `None`.
+pub fn hex_as_ascii(literal: &str) -> Option<String> {
+    HexLiteral::new(literal)?.as_ascii()
+}
+
 /// Hash a string with Keccak256
 pub fn hash_bytes(dest: &mut [u8], to_hash: &String) {
     let mut hasher = Keccak::v256();
@@ -70,12 +70,14 @@ pub struct LexicalError<'a> {
     pub kind: LexicalErrorKind<'a>,
     /// The span where the error occurred
     pub span: Span,
+    /// Hints about the error
+    pub hint: Option<String>,
 }
 
 impl<'a> LexicalError<'a> {
     /// Public associated function to instatiate a new LexicalError.
     pub fn new(kind: LexicalErrorKind<'a>, span: Span) -> Self {
-        Self { kind, span }
+        Self { kind, span, hint: None }
     }
 }
 
@@ -91,6 +93,17 @@ pub enum LexicalErrorKind<'a> {
     InvalidArraySize(&'a str),
     /// Invalid Primitive EVM Type
     InvalidPrimitiveType(&'a str),
+    /// Unexpected token
+    /// String param is the raw source of the offending token
+    UnexpectedToken(&'a str),
+    /// Decimal literal with a leading zero
+    LeadingZero,
+    /// Decorator opened with `#[` but never closed
+    UnclosedDecorator,
+    /// Hex literal wider than 32 bytes
+    OversizedLiteral,
+    /// Decimal or octal literal that does not fit in a `usize`
+    NumOverflow,
 }
 
 impl<'a> Spanned for LexicalError<'a> {
@@ -110,6 +123,75 @@ impl<'a, W: Write> Report<W> for LexicalError<'a> {
             LexicalErrorKind::InvalidPrimitiveType(str) => {
                 write!(f.out, "Invalid Primitive EVM Type '{}'", str)
             }
+            LexicalErrorKind::UnexpectedToken(str) => write!(f.out, "Unexpected token '{}'", str),
+            LexicalErrorKind::LeadingZero => write!(f.out, "Leading zero in decimal literal"),
+            LexicalErrorKind::UnclosedDecorator => write!(f.out, "Unclosed decorator"),
+            LexicalErrorKind::OversizedLiteral => write!(f.out, "Hex literal wider than 32 bytes"),
+            LexicalErrorKind::NumOverflow => write!(f.out, "Numeric literal overflow"),
+        }
+    }
+}
+
+/// A Lexing Warning
+///
+/// Warnings flag suspicious, but lexically valid, source code.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LexicalWarning {
+    /// The kind of warning
+    pub kind: LexicalWarningKind,
+    /// The span the warning points at
+    pub span: Span,
+}
+
+impl LexicalWarning {
+    /// Public associated function to instatiate a new LexicalWarning.
+    pub fn new(kind: LexicalWarningKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// A Lexical Warning Kind
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum LexicalWarningKind {
+    /// Hex literal compared with `eq` that is not a 4 byte function selector
+    SuspiciousSelector,
+    /// Constant literal wider than 32 bytes
+    OversizedConstant,
+    /// Non-ASCII whitespace adjacent to a numeric literal
+    SuspiciousWhitespaceInLiteral,
+    /// Code following a terminating opcode that no label makes reachable
+    UnreachableCode,
+    /// Hex literal prefix with an uppercase letter (`0X`)
+    UppercaseRadixPrefix,
+    /// Numeric literal directly followed by an identifier character
+    AmbiguousLiteral,
+}
+
+impl Spanned for LexicalWarning {
+    fn span(&self) -> Span {
+        self.span.clone()
+    }
+}
+
+impl<W: Write> Report<W> for LexicalWarning {
+    fn report(&self, f: &mut Reporter<'_, W>) -> std::io::Result<()> {
+        match self.kind {
+            LexicalWarningKind::SuspiciousSelector => {
+                write!(f.out, "Compared literal is not a 4 byte selector")
+            }
+            LexicalWarningKind::OversizedConstant => {
+                write!(f.out, "Constant literal is wider than 32 bytes")
+            }
+            LexicalWarningKind::SuspiciousWhitespaceInLiteral => {
+                write!(f.out, "Non-ASCII whitespace next to a numeric literal")
+            }
+            LexicalWarningKind::UnreachableCode => write!(f.out, "Unreachable code"),
+            LexicalWarningKind::UppercaseRadixPrefix => {
+                write!(f.out, "Uppercase radix prefix, use a lowercase letter instead")
+            }
+            LexicalWarningKind::AmbiguousLiteral => {
+                write!(f.out, "Numeric literal directly followed by an identifier")
+            }
         }
     }
 }
@@ -260,43 +342,91 @@ pub enum CompilerError<'a> {
 impl<'a> fmt::Display for CompilerError<'a> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            CompilerError::LexicalError(le) => match le.kind {
-                LexicalErrorKind::UnexpectedEof => {
-                    write!(
-                        f,
-                        "\nError: Unexpected End Of File {}{}\n",
-                        le.span.identifier(),
-                        le.span.source_seg()
-                    )
+            CompilerError::LexicalError(le) => {
+                match le.kind {
+                    LexicalErrorKind::UnexpectedEof => {
+                        write!(
+                            f,
+                            "\nError: Unexpected End Of File {}{}\n",
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                    LexicalErrorKind::InvalidCharacter(c) => {
+                        write!(
+                            f,
+                            "\nError: Invalid Character: \"{}\" {}{}\n",
+                            c,
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                    LexicalErrorKind::InvalidArraySize(a) => {
+                        write!(
+                            f,
+                            "\nError: Invalid Array Size: \"{}\" {}{}\n",
+                            a,
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                    LexicalErrorKind::InvalidPrimitiveType(ty) => {
+                        write!(
+                            f,
+                            "\nError: Invalid Primitive Type: \"{}\" {}{}\n",
+                            ty,
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                    LexicalErrorKind::UnexpectedToken(tok) => {
+                        write!(
+                            f,
+                            "\nError: Unexpected Token: \"{}\" {}{}\n",
+                            tok,
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                    LexicalErrorKind::LeadingZero => {
+                        write!(
+                            f,
+                            "\nError: Leading Zero In Decimal Literal {}{}\n",
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                    LexicalErrorKind::UnclosedDecorator => {
+                        write!(
+                            f,
+                            "\nError: Unclosed Decorator {}{}\n",
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                    LexicalErrorKind::OversizedLiteral => {
+                        write!(
+                            f,
+                            "\nError: Hex Literal Wider Than 32 Bytes {}{}\n",
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                    LexicalErrorKind::NumOverflow => {
+                        write!(
+                            f,
+                            "\nError: Numeric Literal Overflow {}{}\n",
+                            le.span.identifier(),
+                            le.span.source_seg()
+                        )
+                    }
+                }?;
+                // Add in optional hint message
+                match &le.hint {
+                    Some(hint) => writeln!(f, "{}", hint),
+                    None => Ok(()),
                 }
-                LexicalErrorKind::InvalidCharacter(c) => {
-                    write!(
-                        f,
-                        "\nError: Invalid Character: \"{}\" {}{}\n",
-                        c,
-                        le.span.identifier(),
-                        le.span.source_seg()
-                    )
-                }
-                LexicalErrorKind::InvalidArraySize(a) => {
-                    write!(
-                        f,
-                        "\nError: Invalid Array Size: \"{}\" {}{}\n",
-                        a,
-                        le.span.identifier(),
-                        le.span.source_seg()
-                    )
-                }
-                LexicalErrorKind::InvalidPrimitiveType(ty) => {
-                    write!(
-                        f,
-                        "\nError: Invalid Primitive Type: \"{}\" {}{}\n",
-                        ty,
-                        le.span.identifier(),
-                        le.span.source_seg()
-                    )
-                }
-            },
+            }
             CompilerError::FileUnpackError(ue) => match ue {
                 UnpackError::InvalidDirectory(id) => {
                     write!(f, "\nError: Invalid File Directory {}\n", id)
@@ -1,4 +1,6 @@
+use crate::token::{Token, TokenKind};
 use phf::phf_map;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use strum_macros::EnumString;
 
@@ -303,7 +305,9 @@ pub static OPCODES_MAP: phf::Map<&'static str, Opcode> = phf_map! {
 
 /// EVM Opcodes
 /// References <https://evm.codes>
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumString)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumString, Serialize, Deserialize,
+)]
 #[strum(serialize_all = "lowercase")]
 pub enum Opcode {
     /// Halts execution.
@@ -434,6 +438,9 @@ pub enum Opcode {
     Gas,
     /// Marks a valid destination for jumps
     Jumpdest,
+    /// Places the constant value 0 on top of the stack
+    /// Only produced by `minimal_push_opcode`: `push0` is not lexed as an Opcode.
+    Push0,
     /// Places 1 byte item on top of the stack
     Push1,
     /// Places 2 byte item on top of the stack
@@ -597,7 +604,319 @@ pub enum Opcode {
     Extcodehash,
 }
 
+/// The categories EVM Opcodes are grouped in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OpcodeCategory {
+    /// Arithmetic operations
+    Arithmetic,
+    /// Comparison operations
+    Comparison,
+    /// Bitwise logic operations
+    Bitwise,
+    /// Hashing operations
+    Hashing,
+    /// Memory operations
+    Memory,
+    /// Storage operations
+    Storage,
+    /// Stack manipulation operations
+    Stack,
+    /// Control flow and halting operations
+    ControlFlow,
+    /// Account creation and message call operations
+    System,
+    /// Logging operations
+    Logging,
+    /// Execution environment and block information
+    Environment,
+}
+
 impl Opcode {
+    /// Returns the category of the Opcode
+    pub fn category(&self) -> OpcodeCategory {
+        match self {
+            Opcode::Add |
+            Opcode::Mul |
+            Opcode::Sub |
+            Opcode::Div |
+            Opcode::Sdiv |
+            Opcode::Mod |
+            Opcode::Smod |
+            Opcode::Addmod |
+            Opcode::Mulmod |
+            Opcode::Exp |
+            Opcode::Signextend => OpcodeCategory::Arithmetic,
+            Opcode::Lt | Opcode::Gt | Opcode::Slt | Opcode::Sgt | Opcode::Eq | Opcode::Iszero => {
+                OpcodeCategory::Comparison
+            }
+            Opcode::And |
+            Opcode::Or |
+            Opcode::Xor |
+            Opcode::Not |
+            Opcode::Byte |
+            Opcode::Shl |
+            Opcode::Shr |
+            Opcode::Sar => OpcodeCategory::Bitwise,
+            Opcode::Sha3 => OpcodeCategory::Hashing,
+            Opcode::Address |
+            Opcode::Balance |
+            Opcode::Origin |
+            Opcode::Caller |
+            Opcode::Callvalue |
+            Opcode::Calldataload |
+            Opcode::Calldatasize |
+            Opcode::Calldatacopy |
+            Opcode::Codesize |
+            Opcode::Codecopy |
+            Opcode::Gasprice |
+            Opcode::Extcodesize |
+            Opcode::Extcodecopy |
+            Opcode::Returndatasize |
+            Opcode::Returndatacopy |
+            Opcode::Extcodehash |
+            Opcode::Blockhash |
+            Opcode::Coinbase |
+            Opcode::Timestamp |
+            Opcode::Number |
+            Opcode::Difficulty |
+            Opcode::Prevrandao |
+            Opcode::Gaslimit |
+            Opcode::Chainid |
+            Opcode::Selfbalance |
+            Opcode::Basefee |
+            Opcode::Gas => OpcodeCategory::Environment,
+            Opcode::Mload | Opcode::Mstore | Opcode::Mstore8 | Opcode::Msize => {
+                OpcodeCategory::Memory
+            }
+            Opcode::Sload | Opcode::Sstore => OpcodeCategory::Storage,
+            Opcode::Stop |
+            Opcode::Jump |
+            Opcode::Jumpi |
+            Opcode::Pc |
+            Opcode::Jumpdest |
+            Opcode::Return |
+            Opcode::Revert |
+            Opcode::Invalid => OpcodeCategory::ControlFlow,
+            Opcode::Pop |
+            Opcode::Push0 |
+            Opcode::Push1 |
+            Opcode::Push2 |
+            Opcode::Push3 |
+            Opcode::Push4 |
+            Opcode::Push5 |
+            Opcode::Push6 |
+            Opcode::Push7 |
+            Opcode::Push8 |
+            Opcode::Push9 |
+            Opcode::Push10 |
+            Opcode::Push11 |
+            Opcode::Push12 |
+            Opcode::Push13 |
+            Opcode::Push14 |
+            Opcode::Push15 |
+            Opcode::Push16 |
+            Opcode::Push17 |
+            Opcode::Push18 |
+            Opcode::Push19 |
+            Opcode::Push20 |
+            Opcode::Push21 |
+            Opcode::Push22 |
+            Opcode::Push23 |
+            Opcode::Push24 |
+            Opcode::Push25 |
+            Opcode::Push26 |
+            Opcode::Push27 |
+            Opcode::Push28 |
+            Opcode::Push29 |
+            Opcode::Push30 |
+            Opcode::Push31 |
+            Opcode::Push32 |
+            Opcode::Dup1 |
+            Opcode::Dup2 |
+            Opcode::Dup3 |
+            Opcode::Dup4 |
+            Opcode::Dup5 |
+            Opcode::Dup6 |
+            Opcode::Dup7 |
+            Opcode::Dup8 |
+            Opcode::Dup9 |
+            Opcode::Dup10 |
+            Opcode::Dup11 |
+            Opcode::Dup12 |
+            Opcode::Dup13 |
+            Opcode::Dup14 |
+            Opcode::Dup15 |
+            Opcode::Dup16 |
+            Opcode::Swap1 |
+            Opcode::Swap2 |
+            Opcode::Swap3 |
+            Opcode::Swap4 |
+            Opcode::Swap5 |
+            Opcode::Swap6 |
+            Opcode::Swap7 |
+            Opcode::Swap8 |
+            Opcode::Swap9 |
+            Opcode::Swap10 |
+            Opcode::Swap11 |
+            Opcode::Swap12 |
+            Opcode::Swap13 |
+            Opcode::Swap14 |
+            Opcode::Swap15 |
+            Opcode::Swap16 => OpcodeCategory::Stack,
+            Opcode::Log0 | Opcode::Log1 | Opcode::Log2 | Opcode::Log3 | Opcode::Log4 => {
+                OpcodeCategory::Logging
+            }
+            Opcode::Create |
+            Opcode::Call |
+            Opcode::Callcode |
+            Opcode::Delegatecall |
+            Opcode::Create2 |
+            Opcode::Staticcall |
+            Opcode::Selfdestruct => OpcodeCategory::System,
+        }
+    }
+
+    /// Returns the number of stack items the Opcode pops and pushes, in that order
+    pub fn stack_io(&self) -> (u8, u8) {
+        match self {
+            Opcode::Stop | Opcode::Jumpdest | Opcode::Invalid => (0, 0),
+            Opcode::Add |
+            Opcode::Mul |
+            Opcode::Sub |
+            Opcode::Div |
+            Opcode::Sdiv |
+            Opcode::Mod |
+            Opcode::Smod |
+            Opcode::Exp |
+            Opcode::Signextend |
+            Opcode::Lt |
+            Opcode::Gt |
+            Opcode::Slt |
+            Opcode::Sgt |
+            Opcode::Eq |
+            Opcode::And |
+            Opcode::Or |
+            Opcode::Xor |
+            Opcode::Byte |
+            Opcode::Shl |
+            Opcode::Shr |
+            Opcode::Sar |
+            Opcode::Sha3 => (2, 1),
+            Opcode::Addmod | Opcode::Mulmod | Opcode::Create => (3, 1),
+            Opcode::Iszero |
+            Opcode::Not |
+            Opcode::Balance |
+            Opcode::Calldataload |
+            Opcode::Extcodesize |
+            Opcode::Extcodehash |
+            Opcode::Blockhash |
+            Opcode::Mload |
+            Opcode::Sload => (1, 1),
+            Opcode::Address |
+            Opcode::Origin |
+            Opcode::Caller |
+            Opcode::Callvalue |
+            Opcode::Calldatasize |
+            Opcode::Codesize |
+            Opcode::Gasprice |
+            Opcode::Returndatasize |
+            Opcode::Coinbase |
+            Opcode::Timestamp |
+            Opcode::Number |
+            Opcode::Difficulty |
+            Opcode::Prevrandao |
+            Opcode::Gaslimit |
+            Opcode::Chainid |
+            Opcode::Selfbalance |
+            Opcode::Basefee |
+            Opcode::Pc |
+            Opcode::Msize |
+            Opcode::Gas |
+            Opcode::Push0 |
+            Opcode::Push1 |
+            Opcode::Push2 |
+            Opcode::Push3 |
+            Opcode::Push4 |
+            Opcode::Push5 |
+            Opcode::Push6 |
+            Opcode::Push7 |
+            Opcode::Push8 |
+            Opcode::Push9 |
+            Opcode::Push10 |
+            Opcode::Push11 |
+            Opcode::Push12 |
+            Opcode::Push13 |
+            Opcode::Push14 |
+            Opcode::Push15 |
+            Opcode::Push16 |
+            Opcode::Push17 |
+            Opcode::Push18 |
+            Opcode::Push19 |
+            Opcode::Push20 |
+            Opcode::Push21 |
+            Opcode::Push22 |
+            Opcode::Push23 |
+            Opcode::Push24 |
+            Opcode::Push25 |
+            Opcode::Push26 |
+            Opcode::Push27 |
+            Opcode::Push28 |
+            Opcode::Push29 |
+            Opcode::Push30 |
+            Opcode::Push31 |
+            Opcode::Push32 => (0, 1),
+            Opcode::Calldatacopy | Opcode::Codecopy | Opcode::Returndatacopy => (3, 0),
+            Opcode::Extcodecopy => (4, 0),
+            Opcode::Pop | Opcode::Jump | Opcode::Selfdestruct => (1, 0),
+            Opcode::Mstore |
+            Opcode::Mstore8 |
+            Opcode::Sstore |
+            Opcode::Jumpi |
+            Opcode::Return |
+            Opcode::Revert |
+            Opcode::Log0 => (2, 0),
+            Opcode::Log1 => (3, 0),
+            Opcode::Log2 => (4, 0),
+            Opcode::Log3 => (5, 0),
+            Opcode::Log4 => (6, 0),
+            Opcode::Call | Opcode::Callcode => (7, 1),
+            Opcode::Delegatecall | Opcode::Staticcall => (6, 1),
+            Opcode::Create2 => (4, 1),
+            Opcode::Dup1 => (1, 2),
+            Opcode::Dup2 => (2, 3),
+            Opcode::Dup3 => (3, 4),
+            Opcode::Dup4 => (4, 5),
+            Opcode::Dup5 => (5, 6),
+            Opcode::Dup6 => (6, 7),
+            Opcode::Dup7 => (7, 8),
+            Opcode::Dup8 => (8, 9),
+            Opcode::Dup9 => (9, 10),
+            Opcode::Dup10 => (10, 11),
+            Opcode::Dup11 => (11, 12),
+            Opcode::Dup12 => (12, 13),
+            Opcode::Dup13 => (13, 14),
+            Opcode::Dup14 => (14, 15),
+            Opcode::Dup15 => (15, 16),
+            Opcode::Dup16 => (16, 17),
+            Opcode::Swap1 => (2, 2),
+            Opcode::Swap2 => (3, 3),
+            Opcode::Swap3 => (4, 4),
+            Opcode::Swap4 => (5, 5),
+            Opcode::Swap5 => (6, 6),
+            Opcode::Swap6 => (7, 7),
+            Opcode::Swap7 => (8, 8),
+            Opcode::Swap8 => (9, 9),
+            Opcode::Swap9 => (10, 10),
+            Opcode::Swap10 => (11, 11),
+            Opcode::Swap11 => (12, 12),
+            Opcode::Swap12 => (13, 13),
+            Opcode::Swap13 => (14, 14),
+            Opcode::Swap14 => (15, 15),
+            Opcode::Swap15 => (16, 16),
+            Opcode::Swap16 => (17, 17),
+        }
+    }
+
     /// Translates an Opcode into a string
     pub fn string(&self) -> String {
         let opcode_str = match self {
@@ -666,6 +985,7 @@ impl Opcode {
             Opcode::Msize => "59",
             Opcode::Gas => "5a",
             Opcode::Jumpdest => "5b",
+            Opcode::Push0 => "5f",
             Opcode::Push1 => "60",
             Opcode::Push2 => "61",
             Opcode::Push3 => "62",
@@ -762,3 +1082,44 @@ impl From<Opcode> for String {
         o.string()
     }
 }
+
+/// The EVM hard forks that change the available Opcodes
+///
+/// There is no default version: callers choose the fork they target explicitly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum EVMVersion {
+    /// Paris, the last fork without `push0`
+    Paris,
+    /// Shanghai, which introduced `push0`
+    Shanghai,
+}
+
+impl EVMVersion {
+    /// Whether the `push0` Opcode is available
+    pub fn has_push0(&self) -> bool {
+        *self >= EVMVersion::Shanghai
+    }
+}
+
+/// Returns the smallest push Opcode that fits the value's significant bytes, or `None` if they
+/// are wider than 32 bytes.
+///
+/// Zero is pushed with `push0` when the EVM version has it, and with `push1` otherwise.
+pub fn minimal_push_opcode(value_bytes: &[u8], evm_version: EVMVersion) -> Option<Opcode> {
+    let width = value_bytes.iter().skip_while(|b| **b == 0).count();
+    match width {
+        0 if evm_version.has_push0() => Some(Opcode::Push0),
+        0 => Some(Opcode::Push1),
+        1..=32 => Some(OPCODES_MAP[&format!("push{}", width)]),
+        _ => None,
+    }
+}
+
+/// Returns the smallest push Opcode that fits the value of a `TokenKind::Literal` token, or
+/// `None` for other tokens.
+pub fn minimal_push_opcode_for(token: &Token, evm_version: EVMVersion) -> Option<Opcode> {
+    match &token.kind {
+        TokenKind::Literal(value) => minimal_push_opcode(value, evm_version),
+        _ => None,
+    }
+}
@@ -171,6 +171,64 @@ impl Remapper {
     }
 }
 
+/// The structured form of an `#include` path
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IncludeSpecifier<'a> {
+    /// A path on the local filesystem
+    Local(&'a str),
+    /// A registry path of the form `host/org/repo[@version][/path]`
+    Registry {
+        /// The registry host (e.g. `github.com`)
+        host: &'a str,
+        /// The organization owning the repository
+        org: &'a str,
+        /// The repository name
+        repo: &'a str,
+        /// The pinned version, if any
+        version: Option<&'a str>,
+        /// The path of the file within the repository
+        path: &'a str,
+    },
+}
+
+/// The registry hosts an `#include` path without a scheme may start with
+pub const KNOWN_REGISTRY_HOSTS: [&str; 3] = ["github.com", "gitlab.com", "bitbucket.org"];
+
+/// Parses an `#include` path, kept verbatim, into its structured form.
+///
+/// A path is a registry specifier when it has at least `host/org/repo` segments and either an
+/// explicit scheme (e.g. `https://`) or one of the [KNOWN_REGISTRY_HOSTS]. Any other path,
+/// including one whose first directory contains a `.` (e.g. `lib.v2/a/b`), is local.
+pub fn parse_include_specifier(path: &str) -> IncludeSpecifier<'_> {
+    let (remote, rest) = match path.split_once("://") {
+        Some((scheme, rest)) if !scheme.is_empty() => (true, rest),
+        _ => (false, path),
+    };
+
+    let mut segments = rest.splitn(4, '/');
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some(host), Some(org), Some(repo))
+            if (remote || KNOWN_REGISTRY_HOSTS.contains(&host)) &&
+                !host.is_empty() &&
+                !org.is_empty() &&
+                !repo.is_empty() =>
+        {
+            let (repo, version) = match repo.split_once('@') {
+                Some((repo, version)) => (repo, Some(version)),
+                None => (repo, None),
+            };
+            IncludeSpecifier::Registry {
+                host,
+                org,
+                repo,
+                version,
+                path: segments.next().unwrap_or_default(),
+            }
+        }
+        _ => IncludeSpecifier::Local(path),
+    }
+}
+
 /// File Encapsulation
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub struct FileSource {
@@ -299,6 +357,9 @@ impl Span {
     /// An EOF spans [0, 0].
     pub const EOF: Span = Span { start: 0, end: 0, file: None };
 
+    /// A dummy span marks source-less, synthesized tokens.
+    pub const DUMMY: Span = Span { start: usize::MAX, end: usize::MAX, file: None };
+
     /// Public associated function to instatiate a new span.
     pub fn new(Range { start, end }: Range<usize>, file: Option<Arc<FileSource>>) -> Self {
         Self { start, end, file }
@@ -306,7 +367,12 @@ impl Span {
 
     /// Converts a span to a range.
     pub fn range(&self) -> Option<Range<usize>> {
-        (*self != Self::EOF).then_some(self.start..self.end)
+        (*self != Self::EOF && !self.is_dummy()).then_some(self.start..self.end)
+    }
+
+    /// Checks if the span is the dummy span of a synthesized token.
+    pub fn is_dummy(&self) -> bool {
+        *self == Self::DUMMY
     }
 
     /// Produces a file identifier string for errors
@@ -350,9 +416,11 @@ impl Span {
     }
 }
 
+/// Panics on the `EOF` and dummy spans, which do not cover a range of the source. Use
+/// `Span::range` to handle them.
 impl From<Span> for Range<usize> {
     fn from(span: Span) -> Self {
-        span.range().unwrap()
+        span.range().expect("span does not cover a range of the source")
     }
 }
 
@@ -396,3 +464,48 @@ impl<T> Spanned for WithSpan<T> {
         self.span.clone()
     }
 }
+
+/// Maps offsets in a source normalized by `normalize_line_endings` back to the original source.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SourceMap {
+    /// The normalized offsets of the line feeds that were preceded by a removed carriage return
+    pub removed: Vec<usize>,
+}
+
+impl SourceMap {
+    /// Maps a byte offset in the normalized source to the original source.
+    ///
+    /// An offset at a normalized `\n` maps to the start of the original `\r\n`.
+    pub fn original_offset(&self, offset: usize) -> usize {
+        offset + self.removed.partition_point(|&removed| removed < offset)
+    }
+
+    /// Maps a span of the normalized source to the original source.
+    pub fn original_span(&self, span: &Span) -> Span {
+        Span::new(
+            self.original_offset(span.start)..self.original_offset(span.end),
+            span.file.clone(),
+        )
+    }
+}
+
+/// Converts all line endings (`\r\n` and lone `\r`) to `\n`.
+///
+/// Returns the normalized source along with the map of its offsets to the original source.
+pub fn normalize_line_endings(source: &str) -> (String, SourceMap) {
+    let mut normalized = String::with_capacity(source.len());
+    let mut map = SourceMap::default();
+    let mut chars = source.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch == '\r' {
+            if chars.peek() == Some(&'\n') {
+                map.removed.push(normalized.len());
+            } else {
+                normalized.push('\n');
+            }
+        } else {
+            normalized.push(ch);
+        }
+    }
+    (normalized, map)
+}
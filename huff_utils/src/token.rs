@@ -1,10 +1,25 @@
-use crate::{evm::Opcode, files::Span, types::PrimitiveEVMType};
+use crate::{
+    bytes_util::{str_to_bytes32, str_to_vec},
+    evm::Opcode,
+    files::Span,
+    types::PrimitiveEVMType,
+};
+use serde::{Deserialize, Serialize};
 use std::{fmt, fmt::Write};
 
 type Literal = [u8; 32];
 
+/// A Token that does not borrow from its source.
+///
+/// Tokens already own their text, so this is an alias kept for consumers that store tokens beyond
+/// the lifetime of the source they were lexed from.
+pub type OwnedToken = Token;
+
+/// The kind of an [OwnedToken](OwnedToken).
+pub type OwnedTokenKind = TokenKind;
+
 /// A single Token
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub struct Token {
     /// The kind of token
     pub kind: TokenKind,
@@ -17,10 +32,25 @@ impl Token {
     pub fn new(kind: TokenKind, span: Span) -> Self {
         Self { kind, span }
     }
+
+    /// Instantiates a synthesized Token, not lexed from any source, with a dummy span.
+    pub fn synthetic(kind: TokenKind) -> Self {
+        Self { kind, span: Span::DUMMY }
+    }
+
+    /// Instantiates a Token at the given span, mirroring `Token::synthetic`.
+    pub fn with_span(kind: TokenKind, span: Span) -> Self {
+        Self::new(kind, span)
+    }
+
+    /// Copies the Token into an [OwnedToken](OwnedToken), independent of the lexed source.
+    pub fn to_owned_token(&self) -> OwnedToken {
+        self.clone()
+    }
 }
 
 /// The kind of token
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Serialize, Deserialize)]
 pub enum TokenKind {
     /// EOF Token
     Eof,
@@ -65,6 +95,8 @@ pub enum TokenKind {
     FreeStoragePointer,
     /// An Identifier
     Ident(String),
+    /// A modifier keyword between a macro signature and its body (e.g. `unchecked`)
+    Modifier(String),
     /// Equal Sign
     Assign,
     /// An open parenthesis
@@ -129,6 +161,105 @@ pub enum TokenKind {
     Memory,
     /// Storage Data Location
     Storage,
+    /// "#ifdef" directive with the symbol it checks
+    IfDef(String),
+    /// "#endif" directive
+    EndIf,
+    /// Source disabled by an "#ifdef" directive
+    InactiveRegion,
+}
+
+/// The highlighting category of a token
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum TokenCategory {
+    /// Whitespace and the end of file
+    Whitespace,
+    /// Comments and source disabled by an "#ifdef"
+    Comment,
+    /// Keywords and directives
+    Keyword,
+    /// Identifiers
+    Identifier,
+    /// Jump labels
+    Label,
+    /// Numbers and hex literals
+    Number,
+    /// String literals
+    String,
+    /// Opcodes
+    Opcode,
+    /// EVM types
+    Type,
+    /// Builtin functions
+    Builtin,
+    /// Operators and delimiters
+    Punctuation,
+    /// Source that failed to lex
+    Error,
+}
+
+impl TokenKind {
+    /// Whether the token is an empty string literal (`""` or `''`)
+    pub fn is_empty_str(&self) -> bool {
+        matches!(self, TokenKind::Str(s) if s.is_empty())
+    }
+
+    /// Returns the highlighting category of the token
+    pub fn category(&self) -> TokenCategory {
+        match self {
+            TokenKind::Eof | TokenKind::Whitespace => TokenCategory::Whitespace,
+            TokenKind::Comment(_) | TokenKind::InactiveRegion => TokenCategory::Comment,
+            TokenKind::Define |
+            TokenKind::Include |
+            TokenKind::IfDef(_) |
+            TokenKind::EndIf |
+            TokenKind::Macro |
+            TokenKind::Fn |
+            TokenKind::Test |
+            TokenKind::Function |
+            TokenKind::Event |
+            TokenKind::Constant |
+            TokenKind::Error |
+            TokenKind::Takes |
+            TokenKind::Returns |
+            TokenKind::View |
+            TokenKind::Pure |
+            TokenKind::Payable |
+            TokenKind::NonPayable |
+            TokenKind::Indexed |
+            TokenKind::FreeStoragePointer |
+            TokenKind::JumpTable |
+            TokenKind::JumpTablePacked |
+            TokenKind::CodeTable |
+            TokenKind::Calldata |
+            TokenKind::Memory |
+            TokenKind::Storage |
+            TokenKind::Modifier(_) => TokenCategory::Keyword,
+            TokenKind::Ident(_) => TokenCategory::Identifier,
+            TokenKind::Label(_) => TokenCategory::Label,
+            TokenKind::Num(_) | TokenKind::Literal(_) => TokenCategory::Number,
+            TokenKind::Str(_) => TokenCategory::String,
+            TokenKind::Opcode(_) => TokenCategory::Opcode,
+            TokenKind::PrimitiveType(_) | TokenKind::ArrayType(_, _) => TokenCategory::Type,
+            TokenKind::BuiltinFunction(_) => TokenCategory::Builtin,
+            TokenKind::Div |
+            TokenKind::Assign |
+            TokenKind::OpenParen |
+            TokenKind::CloseParen |
+            TokenKind::OpenBracket |
+            TokenKind::CloseBracket |
+            TokenKind::OpenBrace |
+            TokenKind::CloseBrace |
+            TokenKind::LeftAngle |
+            TokenKind::RightAngle |
+            TokenKind::Add |
+            TokenKind::Sub |
+            TokenKind::Mul |
+            TokenKind::Comma |
+            TokenKind::Colon |
+            TokenKind::Pound => TokenCategory::Punctuation,
+        }
+    }
 }
 
 impl fmt::Display for TokenKind {
@@ -155,6 +286,7 @@ impl fmt::Display for TokenKind {
             TokenKind::Returns => "returns",
             TokenKind::FreeStoragePointer => "FREE_STORAGE_POINTER()",
             TokenKind::Ident(s) => return write!(f, "{}", s),
+            TokenKind::Modifier(s) => return write!(f, "{}", s),
             TokenKind::Assign => "=",
             TokenKind::OpenParen => "(",
             TokenKind::CloseParen => ")",
@@ -198,8 +330,67 @@ impl fmt::Display for TokenKind {
             TokenKind::Calldata => return write!(f, "calldata"),
             TokenKind::Memory => return write!(f, "memory"),
             TokenKind::Storage => return write!(f, "storage"),
+            TokenKind::IfDef(s) => return write!(f, "#ifdef {}", s),
+            TokenKind::EndIf => "#endif",
+            TokenKind::InactiveRegion => "InactiveRegion",
         };
 
         write!(f, "{}", x)
     }
 }
+
+/// A hex literal as written in the source, used to recover the width lost by
+/// `TokenKind::Literal`'s 32 byte value.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HexLiteral<'a> {
+    /// The hex digits, without the `0x` prefix
+    pub digits: &'a str,
+}
+
+impl<'a> HexLiteral<'a> {
+    /// Public associated function that instantiates a HexLiteral from its source, with or
+    /// without the `0x` prefix. Returns `None` if it isn't made of hex digits.
+    pub fn new(literal: &'a str) -> Option<Self> {
+        let digits =
+            literal.strip_prefix("0x").or_else(|| literal.strip_prefix("0X")).unwrap_or(literal);
+        (!digits.is_empty() && digits.chars().all(|c| c.is_ascii_hexdigit()))
+            .then_some(Self { digits })
+    }
+
+    /// Instantiates the HexLiteral of a `TokenKind::Literal` token from the source its span
+    /// refers to. Returns `None` for other tokens, and for the `true` and `false` literals.
+    pub fn from_token(token: &Token, source: &'a str) -> Option<Self> {
+        match token.kind {
+            TokenKind::Literal(_) => Self::new(source.get(token.span.start..token.span.end)?),
+            _ => None,
+        }
+    }
+
+    /// Whether the literal is a 4 byte function selector, i.e. exactly 8 hex digits
+    pub fn is_selector(&self) -> bool {
+        self.digits.len() == 8
+    }
+
+    /// The number of bytes the literal is written with, leading zeros included
+    pub fn byte_width(&self) -> usize {
+        self.digits.len().div_ceil(2)
+    }
+
+    /// The literal's value
+    pub fn value(&self) -> Literal {
+        str_to_bytes32(self.digits)
+    }
+
+    /// The literal's bytes read as an ASCII string, e.g. `"hello"` for `0x68656c6c6f`. Returns
+    /// `None` if the literal has an odd number of digits or if any byte isn't printable ASCII.
+    pub fn as_ascii(&self) -> Option<String> {
+        if !self.digits.len().is_multiple_of(2) {
+            return None
+        }
+        let bytes = str_to_vec(self.digits).ok()?;
+        bytes
+            .iter()
+            .all(|b| b.is_ascii_graphic() || *b == b' ')
+            .then(|| bytes.into_iter().map(char::from).collect())
+    }
+}
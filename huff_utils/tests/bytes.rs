@@ -18,3 +18,13 @@ fn test_hex_to_usize() {
         assert_eq!(converted_usize, i);
     }
 }
+
+#[test]
+fn test_hex_as_ascii() {
+    assert_eq!(hex_as_ascii("0x68656c6c6f"), Some("hello".to_string()));
+    assert_eq!(hex_as_ascii("68656c6c6f20776f726c64"), Some("hello world".to_string()));
+    assert_eq!(hex_as_ascii("0xdeadbeef"), None);
+    // Odd-length and non-hex literals aren't decoded
+    assert_eq!(hex_as_ascii("0x6865c"), None);
+    assert_eq!(hex_as_ascii("0xhello"), None);
+}
@@ -0,0 +1,47 @@
+use huff_utils::prelude::*;
+
+#[test]
+fn test_opcode_category() {
+    assert_eq!(Opcode::Add.category(), OpcodeCategory::Arithmetic);
+    assert_eq!(Opcode::Mstore.category(), OpcodeCategory::Memory);
+    assert_eq!(Opcode::Jumpi.category(), OpcodeCategory::ControlFlow);
+    assert_eq!(Opcode::Log1.category(), OpcodeCategory::Logging);
+}
+
+#[test]
+fn test_categorizes_every_opcode() {
+    for opcode in OPCODES {
+        let _ = OPCODES_MAP.get(opcode).unwrap().category();
+    }
+}
+
+#[test]
+fn test_opcode_stack_io() {
+    assert_eq!(Opcode::Add.stack_io(), (2, 1));
+    assert_eq!(Opcode::Dup1.stack_io(), (1, 2));
+    assert_eq!(Opcode::Pop.stack_io(), (1, 0));
+    assert_eq!(Opcode::Push1.stack_io(), (0, 1));
+    assert_eq!(Opcode::Swap16.stack_io(), (17, 17));
+    assert_eq!(Opcode::Log4.stack_io(), (6, 0));
+    assert_eq!(Opcode::Call.stack_io(), (7, 1));
+}
+
+#[test]
+fn test_minimal_push_opcode() {
+    assert_eq!(minimal_push_opcode(&[0; 32], EVMVersion::Shanghai), Some(Opcode::Push0));
+    assert_eq!(minimal_push_opcode(&[0; 32], EVMVersion::Paris), Some(Opcode::Push1));
+    assert_eq!(minimal_push_opcode(&[0x00, 0x01, 0x02], EVMVersion::Shanghai), Some(Opcode::Push2));
+    assert_eq!(minimal_push_opcode(&[0xff; 32], EVMVersion::Shanghai), Some(Opcode::Push32));
+    // Values wider than 32 bytes can't be pushed
+    assert_eq!(minimal_push_opcode(&[0xff; 33], EVMVersion::Shanghai), None);
+    assert_eq!(minimal_push_opcode(&[0x00; 33], EVMVersion::Shanghai), Some(Opcode::Push0));
+}
+
+#[test]
+fn test_minimal_push_opcode_for_token() {
+    let literal = Token::new(TokenKind::Literal(str_to_bytes32("0102")), Span::new(2..6, None));
+    assert_eq!(minimal_push_opcode_for(&literal, EVMVersion::Paris), Some(Opcode::Push2));
+
+    let ident = Token::new(TokenKind::Ident("FOO".to_string()), Span::new(0..3, None));
+    assert_eq!(minimal_push_opcode_for(&ident, EVMVersion::Paris), None);
+}
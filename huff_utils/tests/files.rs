@@ -111,3 +111,83 @@ fn test_localize_file() {
             .unwrap();
     assert_eq!(localized, "../../../../Address.huff");
 }
+
+#[test]
+fn test_parse_local_include_specifier() {
+    let path = "../huff-examples/erc20/contracts/utils/Ownable.huff";
+    assert_eq!(files::parse_include_specifier(path), files::IncludeSpecifier::Local(path));
+    assert_eq!(
+        files::parse_include_specifier("Ownable.huff"),
+        files::IncludeSpecifier::Local("Ownable.huff")
+    );
+}
+
+#[test]
+fn test_parse_dotted_local_include_specifier() {
+    for path in ["lib.v2/org/repo", "./my.dir/a/b", "my.dir/a/b.huff"] {
+        assert_eq!(files::parse_include_specifier(path), files::IncludeSpecifier::Local(path));
+    }
+}
+
+#[test]
+fn test_parse_registry_include_specifier() {
+    assert_eq!(
+        files::parse_include_specifier("github.com/org/repo@v1/src/lib.huff"),
+        files::IncludeSpecifier::Registry {
+            host: "github.com",
+            org: "org",
+            repo: "repo",
+            version: Some("v1"),
+            path: "src/lib.huff"
+        }
+    );
+
+    // Any host is remote with an explicit scheme
+    assert_eq!(
+        files::parse_include_specifier("https://git.example.org/org/repo/lib.huff"),
+        files::IncludeSpecifier::Registry {
+            host: "git.example.org",
+            org: "org",
+            repo: "repo",
+            version: None,
+            path: "lib.huff"
+        }
+    );
+}
+
+#[test]
+fn test_span_range_round_trip() {
+    let span: Span = (3..7).into();
+    assert_eq!(span, Span::new(3..7, None));
+    let range: std::ops::Range<usize> = span.into();
+    assert_eq!(range, 3..7);
+}
+
+#[test]
+fn test_eof_span_has_no_range() {
+    // The EOF span does not cover any source
+    assert_eq!(Span::EOF.range(), None);
+}
+
+#[test]
+#[should_panic(expected = "span does not cover a range of the source")]
+fn test_eof_span_does_not_convert_to_range() {
+    let _: std::ops::Range<usize> = Span::EOF.into();
+}
+
+#[test]
+fn test_normalize_line_endings() {
+    let source = "#define macro A()\r\n// comment\n#define macro B()\r\n\r\n\rB";
+    let (normalized, map) = files::normalize_line_endings(source);
+    assert_eq!(normalized, "#define macro A()\n// comment\n#define macro B()\n\n\nB");
+
+    // Spans in the normalized source map back to the original offsets
+    let normalized_start = normalized.find("macro B").unwrap();
+    let span = map.original_span(&Span::new(normalized_start..normalized_start + 7, None));
+    assert_eq!(&source[span.start..span.end], "macro B");
+
+    // A normalized line feed maps to the start of its original line ending
+    assert_eq!(map.original_offset(17), 17);
+    assert_eq!(&source[map.original_offset(17)..map.original_offset(18)], "\r\n");
+    assert_eq!(map.original_offset(normalized.len()), source.len());
+}
@@ -0,0 +1,20 @@
+use huff_utils::prelude::*;
+
+#[test]
+fn test_synthetic_token() {
+    let token = Token::synthetic(TokenKind::Eof);
+    assert_eq!(token.kind, TokenKind::Eof);
+    assert!(token.span.is_dummy());
+    assert_eq!(token.span.range(), None);
+
+    // Lexed tokens do not have a dummy span, even at the start of the source
+    assert!(!Token::new(TokenKind::Eof, Span::EOF).span.is_dummy());
+}
+
+#[test]
+fn test_token_with_span() {
+    let token = Token::with_span(TokenKind::Define, Span::new(0..7, None));
+    assert_eq!(token, Token::new(TokenKind::Define, Span::new(0..7, None)));
+    assert_eq!(token.span.range(), Some(0..7));
+    assert!(!token.span.is_dummy());
+}